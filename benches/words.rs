@@ -0,0 +1,79 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use bip39::{language::Words, Language, Mnemonic};
+
+// Vector from https://github.com/trezor/python-mnemonic/blob/master/vectors.json
+const PHRASE: &str = "void come effort suffer camp survey warrior heavy shoot primary clutch crush open amazing screen patrol group space point ten exist slush involve unfold";
+
+///
+/// Word to index lookups, compared with the linear scan of the whole list used before
+///
+fn index_of(c: &mut Criterion) {
+    for lang in [
+        Language::English,
+        Language::French,
+        Language::ChineseSimplified,
+    ] {
+        let words = Words::load(lang.clone());
+        let list = (0..2048)
+            .map(|index| words.word(index))
+            .collect::<Vec<&str>>();
+        let lookups = (0..2048)
+            .step_by(97)
+            .map(|index| words.word(index))
+            .collect::<Vec<&str>>();
+
+        c.bench_function(&format!("index_of {:?}", lang), |b| {
+            b.iter(|| {
+                lookups
+                    .iter()
+                    .map(|word| words.index_of(black_box(word)))
+                    .collect::<Vec<Option<u16>>>()
+            })
+        });
+        c.bench_function(&format!("linear scan {:?}", lang), |b| {
+            b.iter(|| {
+                lookups
+                    .iter()
+                    .map(|word| {
+                        list.iter()
+                            .position(|w| w == black_box(word))
+                            .map(|index| index as u16)
+                    })
+                    .collect::<Vec<Option<u16>>>()
+            })
+        });
+    }
+}
+
+///
+/// Index to word lookups and phrase parsing
+///
+fn word_and_phrase(c: &mut Criterion) {
+    let words = Words::load(Language::English);
+    let phrase_words = PHRASE
+        .split(' ')
+        .map(str::to_owned)
+        .collect::<Vec<String>>();
+
+    c.bench_function("word", |b| {
+        b.iter(|| {
+            (0..2048)
+                .map(|index| words.word(black_box(index)).len())
+                .sum::<usize>()
+        })
+    });
+    c.bench_function("get_index_from_words 24 words", |b| {
+        b.iter(|| {
+            words
+                .get_index_from_words(black_box(&phrase_words))
+                .unwrap()
+        })
+    });
+    c.bench_function("from_phrase 24 words", |b| {
+        b.iter(|| Mnemonic::from_phrase(black_box(PHRASE.to_owned()), Language::English).unwrap())
+    });
+}
+
+criterion_group!(benches, index_of, word_and_phrase);
+criterion_main!(benches);
@@ -0,0 +1,105 @@
+use std::{collections::HashSet, env, fs, path::Path};
+use unicode_normalization::UnicodeNormalization;
+
+///
+/// Words list files embedded into the binary : (file name, is the list sorted ?)
+/// Lists with accents or kana are not sorted by bytes, they are binary searched through a sorted order of indexes
+///
+const WORDS_FILES: [(&str, bool); 10] = [
+    ("english", true),
+    ("french", false),
+    ("italian", true),
+    ("japanese", false),
+    ("korean", true),
+    ("portugese", true),
+    ("spanish", false),
+    ("czech", false),
+    ("chinese_simplified", false),
+    ("chinese_traditional", false),
+];
+
+const NB_WORDS: usize = 2048;
+
+///
+/// SLIP-39 words list file, the list is sorted and every word has a unique 4 letters prefix
+///
+const SLIP39_WORDS_FILE: &str = "slip39";
+const SLIP39_NB_WORDS: usize = 1024;
+
+fn main() {
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is not set");
+    let mut generated = String::new();
+
+    for (name, sorted) in WORDS_FILES {
+        let path = format!("src/words/{}.txt", name);
+        println!("cargo:rerun-if-changed={}", path);
+
+        let words = read_words(name, &read_file(&path), NB_WORDS, sorted);
+
+        // Indexes of the words sorted by bytes, to binary search the lists not sorted by bytes
+        let order = match sorted {
+            true => None,
+            false => {
+                let mut order = (0..NB_WORDS as u16).collect::<Vec<u16>>();
+                order.sort_by(|a, b| words[*a as usize].cmp(&words[*b as usize]));
+                Some(order)
+            }
+        };
+
+        generated.push_str(&format!(
+            "pub static {}: [&str; {}] = {:?};\npub static {}_ORDER: Option<[u16; {}]> = {:?};\n",
+            name.to_uppercase(),
+            NB_WORDS,
+            words,
+            name.to_uppercase(),
+            NB_WORDS,
+            order
+        ));
+    }
+
+    fs::write(Path::new(&out_dir).join("words.rs"), generated)
+        .expect("Error when writing the generated words lists");
+
+    let path = format!("src/words/{}.txt", SLIP39_WORDS_FILE);
+    println!("cargo:rerun-if-changed={}", path);
+
+    let words = read_words(SLIP39_WORDS_FILE, &read_file(&path), SLIP39_NB_WORDS, true);
+    fs::write(
+        Path::new(&out_dir).join("slip39_words.rs"),
+        format!("pub static SLIP39: [&str; {}] = {:?};\n", SLIP39_NB_WORDS, words),
+    )
+    .expect("Error when writing the generated SLIP-39 words list");
+}
+
+fn read_file(path: &str) -> String {
+    fs::read_to_string(path).unwrap_or_else(|e| panic!("Error when reading file {} : {}", path, e))
+}
+
+///
+/// Split and normalize (NFKD) the words, then check the list is a valid words list
+///
+fn read_words(name: &str, content: &str, nb_words: usize, sorted: bool) -> Vec<String> {
+    let words: Vec<String> = content
+        .split('\n')
+        .map(|word| word.trim().nfkd().collect::<String>())
+        .collect();
+
+    if words.len() != nb_words {
+        panic!("The {} words list has {} words instead of {}", name, words.len(), nb_words);
+    }
+
+    if words.iter().any(|word| word.is_empty()) {
+        panic!("The {} words list contains an empty word", name);
+    }
+
+    let mut unique_words = HashSet::new();
+    if let Some(word) = words.iter().find(|word| !unique_words.insert(word.as_str())) {
+        panic!("The {} words list contains the word '{}' twice", name, word);
+    }
+
+    if sorted && words.windows(2).any(|pair| pair[0] > pair[1]) {
+        panic!("The {} words list is not sorted", name);
+    }
+
+    words
+}
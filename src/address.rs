@@ -0,0 +1,115 @@
+use crate::bip32;
+
+/* Config */
+const P2PKH_VERSION: u8 = 0x00;
+const BECH32_HRP: &str = "bc";
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32_GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+const WITNESS_VERSION: u8 = 0;
+
+///
+/// Legacy mainnet address "1...", Base58Check(0x00 || RIPEMD160(SHA256(public key)))
+///
+pub fn p2pkh_address(public_key: &[u8]) -> String {
+    let mut data = vec![P2PKH_VERSION];
+    data.extend_from_slice(&bip32::hash160(public_key));
+
+    bs58::encode(data).with_check().into_string()
+}
+
+///
+/// Native segwit mainnet address "bc1q...", Bech32 of the witness version 0 and the public key hash
+/// From documentation (<https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki>)
+///
+pub fn p2wpkh_address(public_key: &[u8]) -> String {
+    let mut data = vec![WITNESS_VERSION];
+    data.extend(to_5_bits(&bip32::hash160(public_key)));
+
+    let mut values = hrp_expand(BECH32_HRP);
+    values.extend_from_slice(&data);
+    values.extend_from_slice(&[0; 6]);
+    let polymod = bech32_polymod(&values) ^ 1;
+    data.extend((0..6).map(|i| ((polymod >> (5 * (5 - i))) & 31) as u8));
+
+    let encoded = data
+        .iter()
+        .map(|value| BECH32_CHARSET[*value as usize] as char)
+        .collect::<String>();
+
+    format!("{}1{}", BECH32_HRP, encoded)
+}
+
+///
+/// Is the address one of the addresses (P2PKH or P2WPKH) of the public key
+///
+pub fn is_address_of(address: &str, public_key: &[u8]) -> bool {
+    address == p2pkh_address(public_key) || address.to_lowercase() == p2wpkh_address(public_key)
+}
+
+///
+/// Regroup the bytes into groups of 5 bits, the last group is padded with 0
+///
+fn to_5_bits(bytes: &[u8]) -> Vec<u8> {
+    let mut groups = vec![];
+    let mut accumulator = 0u32;
+    let mut nb_bits = 0;
+
+    for byte in bytes {
+        accumulator = (accumulator << 8) | *byte as u32;
+        nb_bits += 8;
+        while nb_bits >= 5 {
+            nb_bits -= 5;
+            groups.push(((accumulator >> nb_bits) & 31) as u8);
+        }
+    }
+    if nb_bits > 0 {
+        groups.push(((accumulator << (5 - nb_bits)) & 31) as u8);
+    }
+
+    groups
+}
+
+fn hrp_expand(hrp: &str) -> Vec<u8> {
+    let mut values = hrp.bytes().map(|c| c >> 5).collect::<Vec<u8>>();
+    values.push(0);
+    values.extend(hrp.bytes().map(|c| c & 31));
+    values
+}
+
+fn bech32_polymod(values: &[u8]) -> u32 {
+    values.iter().fold(1u32, |checksum, value| {
+        let top = checksum >> 25;
+        let checksum = ((checksum & 0x1ffffff) << 5) ^ *value as u32;
+
+        BECH32_GENERATOR
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| (top >> i) & 1 == 1)
+            .fold(checksum, |checksum, (_, generator)| checksum ^ generator)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{p2pkh_address, p2wpkh_address};
+
+    ///
+    /// Addresses of the public key of the private key 1 (generator point)
+    /// From documentation (<https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki#examples>)
+    ///
+    #[test]
+    fn encode_addresses() {
+        let public_key =
+            hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap();
+
+        assert_eq!(
+            p2pkh_address(&public_key),
+            "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH"
+        );
+        assert_eq!(
+            p2wpkh_address(&public_key),
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+        );
+    }
+}
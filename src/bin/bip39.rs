@@ -0,0 +1,337 @@
+//! Command line tool to generate, validate and convert BIP39 mnemonics.
+//! The secrets (phrase, entropy, passphrase) are never read from the arguments, where they would be
+//! visible in the shell history and the process list : they are read line by line from the standard
+//! input, or prompted without echo when the standard input is a terminal
+
+use std::{
+    io::{self, BufRead, IsTerminal},
+    process::ExitCode,
+};
+
+use bip39::{entropy::Bytes, BIP32Error, Entropy, Language, Mnemonic, WordsCount};
+use clap::{Parser, Subcommand, ValueEnum};
+use zeroize::Zeroizing;
+
+#[derive(Parser)]
+#[command(
+    name = "bip39",
+    version,
+    about = "Generate, validate and convert BIP39 mnemonics"
+)]
+struct Cli {
+    /// Print the result as a JSON object
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generate a new mnemonic with the OS random generator
+    Generate {
+        /// Number of words : 12, 15, 18, 21 or 24
+        #[arg(long, default_value_t = 24)]
+        words: usize,
+        #[arg(long, value_enum, default_value_t = Lang::English)]
+        lang: Lang,
+    },
+    /// Check the words and the checksum of the mnemonic read from the standard input
+    Validate {
+        /// Language of the mnemonic, detected when not given
+        #[arg(long, value_enum)]
+        lang: Option<Lang>,
+    },
+    /// Print the entropy of the mnemonic read from the standard input
+    Entropy {
+        /// Language of the mnemonic, detected when not given
+        #[arg(long, value_enum)]
+        lang: Option<Lang>,
+    },
+    /// Create the mnemonic of the hexadecimal entropy read from the standard input
+    FromEntropy {
+        #[arg(long, value_enum, default_value_t = Lang::English)]
+        lang: Lang,
+    },
+    /// Print the seed of the mnemonic read from the standard input
+    Seed {
+        /// Language of the mnemonic, detected when not given
+        #[arg(long, value_enum)]
+        lang: Option<Lang>,
+        /// Read the passphrase from the line following the mnemonic (or prompt it on a terminal)
+        #[arg(long)]
+        passphrase_stdin: bool,
+    },
+    /// Write the entropy of the mnemonic read from the standard input with the words of another language
+    Translate {
+        /// Language of the mnemonic, detected when not given
+        #[arg(long, value_enum)]
+        lang: Option<Lang>,
+        /// Language of the translated mnemonic
+        #[arg(long, value_enum)]
+        to: Lang,
+    },
+}
+
+///
+/// Languages of the embedded words lists
+///
+#[derive(Clone, Copy, ValueEnum)]
+enum Lang {
+    English,
+    French,
+    Italian,
+    Japanese,
+    Korean,
+    #[value(alias = "portuguese")]
+    Portugese,
+    Spanish,
+    Czech,
+    ChineseSimplified,
+    ChineseTraditional,
+}
+
+impl From<Lang> for Language {
+    fn from(lang: Lang) -> Self {
+        match lang {
+            Lang::English => Language::English,
+            Lang::French => Language::French,
+            Lang::Italian => Language::Italian,
+            Lang::Japanese => Language::Japanese,
+            Lang::Korean => Language::Korean,
+            Lang::Portugese => Language::Portugese,
+            Lang::Spanish => Language::Spanish,
+            Lang::Czech => Language::Czech,
+            Lang::ChineseSimplified => Language::ChineseSimplified,
+            Lang::ChineseTraditional => Language::ChineseTraditional,
+        }
+    }
+}
+
+impl Lang {
+    ///
+    /// Name of the language on the command line
+    ///
+    fn name(&self) -> String {
+        self.to_possible_value()
+            .map(|value| value.get_name().to_owned())
+            .unwrap_or_default()
+    }
+
+    ///
+    /// Command line language of an embedded words list, None for a custom list
+    ///
+    fn from_language(language: &Language) -> Option<Lang> {
+        Lang::value_variants()
+            .iter()
+            .find(|lang| Language::from(**lang) == *language)
+            .copied()
+    }
+}
+
+///
+/// Value of a printed field, the texts are wiped from memory when dropped
+///
+enum Value {
+    Text(Zeroizing<String>),
+    Number(usize),
+    Bool(bool),
+}
+
+impl Value {
+    fn text(text: String) -> Value {
+        Value::Text(Zeroizing::new(text))
+    }
+
+    fn to_text(&self) -> Zeroizing<String> {
+        match self {
+            Value::Text(text) => text.clone(),
+            Value::Number(number) => Zeroizing::new(number.to_string()),
+            Value::Bool(value) => Zeroizing::new(value.to_string()),
+        }
+    }
+
+    fn to_json(&self) -> Zeroizing<String> {
+        match self {
+            Value::Text(text) => {
+                Zeroizing::new(serde_json::to_string(text.as_str()).unwrap_or_default())
+            }
+            value => value.to_text(),
+        }
+    }
+}
+
+///
+/// Fields printed as "name: value" lines (only the value when there's a single field), or as a JSON object
+///
+struct Report(Vec<(&'static str, Value)>);
+
+impl Report {
+    fn print(&self, json: bool) {
+        let output = Zeroizing::new(match (json, self.0.as_slice()) {
+            (true, fields) => format!(
+                "{{{}}}",
+                fields
+                    .iter()
+                    .map(|(name, value)| format!("\"{}\":{}", name, value.to_json().as_str()))
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ),
+            (false, [(_, value)]) => value.to_text().to_string(),
+            (false, fields) => fields
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, value.to_text().as_str()))
+                .collect::<Vec<String>>()
+                .join("\n"),
+        });
+
+        println!("{}", output.as_str());
+    }
+}
+
+///
+/// Read the secrets line by line from the standard input, or prompt them without echo on a terminal
+///
+struct SecretReader {
+    is_terminal: bool,
+    stdin: io::StdinLock<'static>,
+}
+
+impl SecretReader {
+    fn new() -> SecretReader {
+        SecretReader {
+            is_terminal: io::stdin().is_terminal(),
+            stdin: io::stdin().lock(),
+        }
+    }
+
+    fn read(&mut self, name: &str) -> Result<Zeroizing<String>, String> {
+        let mut line = Zeroizing::new(String::new());
+
+        if self.is_terminal {
+            line = Zeroizing::new(
+                rpassword::prompt_password(format!("{} : ", name))
+                    .map_err(|e| format!("Error when reading the {} : {}", name, e))?,
+            );
+        } else {
+            let nb_bytes = self
+                .stdin
+                .read_line(&mut line)
+                .map_err(|e| format!("Error when reading the {} : {}", name, e))?;
+            if nb_bytes == 0 {
+                return Err(format!("No {} given on the standard input", name));
+            }
+        }
+
+        let len = line.trim_end_matches(['\n', '\r']).len();
+        line.truncate(len);
+        Ok(line)
+    }
+}
+
+///
+/// Parse the phrase with the given language, or with the only language where the phrase is valid
+///
+fn parse_phrase(phrase: &str, lang: Option<Lang>) -> Result<(Mnemonic, Lang), BIP32Error> {
+    if let Some(lang) = lang {
+        return Ok((Mnemonic::from_phrase(phrase.to_owned(), lang.into())?, lang));
+    }
+
+    let mnemonic = Mnemonic::from_phrase_any_language(phrase.to_owned())?;
+    let lang = Lang::from_language(mnemonic.get_language()).ok_or(BIP32Error::UnknownLanguage)?;
+
+    Ok((mnemonic, lang))
+}
+
+fn run(command: Command) -> Result<Report, String> {
+    let mut secrets = SecretReader::new();
+
+    let report = match command {
+        Command::Generate { words, lang } => {
+            if !matches!(words, 12 | 15 | 18 | 21 | 24) {
+                return Err(BIP32Error::InvalidWordsCount(words).message());
+            }
+
+            let mnemonic = bip39::generate_mnemonic(WordsCount::from(words), lang.into(), None)
+                .map_err(|e| e.message())?;
+            Report(vec![
+                ("phrase", Value::text(mnemonic.to_string())),
+                ("language", Value::text(lang.name())),
+                ("words", Value::Number(words)),
+            ])
+        }
+        Command::Validate { lang } => {
+            let phrase = secrets.read("mnemonic")?;
+            let (mnemonic, lang) = parse_phrase(&phrase, lang).map_err(|e| e.message())?;
+            Report(vec![
+                ("valid", Value::Bool(true)),
+                ("language", Value::text(lang.name())),
+                ("words", Value::Number(mnemonic.get_words().len())),
+            ])
+        }
+        Command::Entropy { lang } => {
+            let phrase = secrets.read("mnemonic")?;
+            let (mnemonic, _) = parse_phrase(&phrase, lang).map_err(|e| e.message())?;
+            Report(vec![(
+                "entropy",
+                Value::text(mnemonic.get_entropy().entropy.to_hex()),
+            )])
+        }
+        Command::FromEntropy { lang } => {
+            let hex = secrets.read("entropy")?;
+            // Entropy::from_hex keeps any decoded length, the size is checked with the bytes
+            let bytes = Bytes::from_hex(hex.trim().to_owned()).map_err(|e| e.message())?;
+            let entropy = Entropy::from_bytes_vec(bytes.into_vec()).map_err(|e| e.message())?;
+            let mnemonic = bip39::generate_mnemonic_from_entropy(entropy, lang.into())
+                .map_err(|e| e.message())?;
+            Report(vec![("phrase", Value::text(mnemonic.to_string()))])
+        }
+        Command::Seed {
+            lang,
+            passphrase_stdin,
+        } => {
+            let phrase = secrets.read("mnemonic")?;
+            let (mnemonic, _) = parse_phrase(&phrase, lang).map_err(|e| e.message())?;
+            // The passphrase is moved out of the line read, without copy, get_seed_from_phrase wipes it
+            let passphrase = match passphrase_stdin {
+                true => Some(std::mem::take(&mut *secrets.read("passphrase")?)),
+                false => None,
+            };
+
+            let seed = bip39::get_seed_from_phrase(mnemonic.get_phrase(), passphrase);
+            Report(vec![("seed", Value::text(seed.to_hex()))])
+        }
+        Command::Translate { lang, to } => {
+            let phrase = secrets.read("mnemonic")?;
+            let (mnemonic, _) = parse_phrase(&phrase, lang).map_err(|e| e.message())?;
+            let translated = mnemonic.to_language(to.into()).map_err(|e| e.message())?;
+            Report(vec![
+                ("phrase", Value::text(translated.to_string())),
+                ("language", Value::text(to.name())),
+                // Warn that the translated mnemonic restores another wallet
+                ("same_seed", Value::Bool(mnemonic.has_same_seed(&translated))),
+            ])
+        }
+    };
+
+    Ok(report)
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(cli.command) {
+        Ok(report) => {
+            report.print(cli.json);
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            match cli.json {
+                true => Report(vec![("error", Value::text(message))]).print(true),
+                false => eprintln!("Error : {}", message),
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,655 @@
+use std::{
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+use hmac::{Hmac, Mac};
+use k256::{
+    elliptic_curve::{sec1::ToEncodedPoint, PrimeField},
+    NonZeroScalar, ProjectivePoint, PublicKey, Scalar, SecretKey,
+};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha512};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::{entropy::Bytes, error::BIP32Error, mnemonic::Seed, utils};
+
+/* Config */
+const MASTER_KEY_HMAC: &[u8] = b"Bitcoin seed";
+const HARDENED_OFFSET: u32 = 1 << 31;
+const XPRV_VERSION: [u8; 4] = [0x04, 0x88, 0xAD, 0xE4];
+const XPUB_VERSION: [u8; 4] = [0x04, 0x88, 0xB2, 0x1E];
+const EXTENDED_KEY_LEN: usize = 78;
+pub(crate) const MIN_SEED_LEN: usize = 16;
+pub(crate) const MAX_SEED_LEN: usize = 64;
+
+///
+/// Index of a child key, hardened keys can only be derived from the private key
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChildNumber {
+    /// Index between 0 and 2^31 - 1, written "0"
+    Normal(u32),
+    /// Index between 0 and 2^31 - 1, written "0'" or "0h"
+    Hardened(u32),
+}
+
+impl ChildNumber {
+    ///
+    /// Get the index used in the derivation (2^31 is added to hardened index)
+    ///
+    pub fn to_index(&self) -> u32 {
+        match self {
+            ChildNumber::Normal(index) => *index,
+            ChildNumber::Hardened(index) => index | HARDENED_OFFSET,
+        }
+    }
+
+    ///
+    /// Create the child number from the index used in the derivation
+    ///
+    pub fn from_index(index: u32) -> ChildNumber {
+        if index & HARDENED_OFFSET == 0 {
+            ChildNumber::Normal(index)
+        } else {
+            ChildNumber::Hardened(index ^ HARDENED_OFFSET)
+        }
+    }
+
+    pub fn is_hardened(&self) -> bool {
+        matches!(self, ChildNumber::Hardened(_))
+    }
+
+    ///
+    /// The index must be lower than 2^31, the highest bit is the hardened flag
+    ///
+    pub(crate) fn check(&self) -> Result<(), BIP32Error> {
+        match self {
+            ChildNumber::Normal(index) | ChildNumber::Hardened(index)
+                if index & HARDENED_OFFSET != 0 =>
+            {
+                Err(BIP32Error::InvalidChildNumber(*index))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl FromStr for ChildNumber {
+    type Err = BIP32Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || BIP32Error::InvalidDerivationPath(value.to_owned());
+
+        let child_number = match value.strip_suffix(['\'', 'h', 'H']) {
+            Some(index) => ChildNumber::Hardened(index.parse().map_err(|_| invalid())?),
+            None => ChildNumber::Normal(value.parse().map_err(|_| invalid())?),
+        };
+        child_number.check()?;
+
+        Ok(child_number)
+    }
+}
+
+impl Display for ChildNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChildNumber::Normal(index) => write!(f, "{}", index),
+            ChildNumber::Hardened(index) => write!(f, "{}'", index),
+        }
+    }
+}
+
+///
+/// List of child numbers from the master key, like "m/44'/0'/0'/0/0"
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct DerivationPath {
+    path: Vec<ChildNumber>,
+}
+
+impl DerivationPath {
+    pub fn new(path: Vec<ChildNumber>) -> DerivationPath {
+        DerivationPath { path }
+    }
+
+    pub fn as_slice(&self) -> &[ChildNumber] {
+        &self.path
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = BIP32Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut parts = value.split('/');
+        if parts.next() != Some("m") {
+            return Err(BIP32Error::InvalidDerivationPath(value.to_owned()));
+        }
+
+        Ok(DerivationPath {
+            path: parts.map(ChildNumber::from_str).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl Display for DerivationPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "m")?;
+        for child_number in self.path.iter() {
+            write!(f, "/{}", child_number)?;
+        }
+        Ok(())
+    }
+}
+
+///
+/// BIP32 extended private key (xprv), the private key and the chain code are wiped from memory when dropped
+///
+#[derive(Clone)]
+pub struct ExtendedPrivateKey {
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: ChildNumber,
+    chain_code: [u8; 32],
+    private_key: SecretKey,
+}
+
+impl ExtendedPrivateKey {
+    ///
+    /// Create the master key from the mnemonic seed
+    ///
+    pub fn from_seed(seed: &Seed) -> Result<ExtendedPrivateKey, BIP32Error> {
+        ExtendedPrivateKey::new_master(seed.get_bytes().as_ref())
+    }
+
+    ///
+    /// Create the master key from seed bytes (between 128 and 512 bits)
+    /// From documentation (<https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#master-key-generation>)
+    ///     Calculate I = HMAC-SHA512(Key = "Bitcoin seed", Data = S)
+    ///     Split I into two 32-byte sequences, IL and IR.
+    ///     Use parse256(IL) as master secret key, and IR as master chain code.
+    ///
+    pub fn new_master(seed: &[u8]) -> Result<ExtendedPrivateKey, BIP32Error> {
+        if seed.len() < MIN_SEED_LEN || seed.len() > MAX_SEED_LEN {
+            return Err(BIP32Error::InvalidSeedLength(seed.len()));
+        }
+
+        let (private_key, chain_code) = hmac_sha512_split(MASTER_KEY_HMAC, seed)?;
+
+        Ok(ExtendedPrivateKey {
+            depth: 0,
+            parent_fingerprint: [0; 4],
+            child_number: ChildNumber::Normal(0),
+            chain_code,
+            private_key: SecretKey::from_bytes(private_key.as_slice().into())
+                .map_err(|_| BIP32Error::InvalidPrivateKey)?,
+        })
+    }
+
+    ///
+    /// Create a master key from an existing chain code and private key
+    ///
+    pub(crate) fn from_parts(
+        chain_code: [u8; 32],
+        private_key: &[u8],
+    ) -> Result<ExtendedPrivateKey, BIP32Error> {
+        Ok(ExtendedPrivateKey {
+            depth: 0,
+            parent_fingerprint: [0; 4],
+            child_number: ChildNumber::Normal(0),
+            chain_code,
+            private_key: SecretKey::from_slice(private_key).map_err(|_| BIP32Error::InvalidPrivateKey)?,
+        })
+    }
+
+    ///
+    /// Derive the child private key (CKDpriv)
+    ///
+    pub fn derive_child(&self, child_number: ChildNumber) -> Result<ExtendedPrivateKey, BIP32Error> {
+        child_number.check()?;
+
+        let mut data = Zeroizing::new(Vec::with_capacity(37));
+        if child_number.is_hardened() {
+            data.push(0);
+            data.extend_from_slice(&self.private_key.to_bytes());
+        } else {
+            data.extend_from_slice(&self.public_key_bytes());
+        }
+        data.extend_from_slice(&child_number.to_index().to_be_bytes());
+
+        let (tweak, chain_code) = hmac_sha512_split(&self.chain_code, &data)?;
+        let tweak = parse_scalar(&tweak)?;
+
+        // ki = parse256(IL) + kpar (mod n), invalid if the result is 0
+        let private_key = Option::<NonZeroScalar>::from(NonZeroScalar::new(
+            tweak + *self.private_key.to_nonzero_scalar(),
+        ))
+        .ok_or(BIP32Error::InvalidPrivateKey)?;
+
+        Ok(ExtendedPrivateKey {
+            depth: self.depth.checked_add(1).ok_or(BIP32Error::MaxDepthExceeded)?,
+            parent_fingerprint: self.fingerprint(),
+            child_number,
+            chain_code,
+            private_key: SecretKey::from(private_key),
+        })
+    }
+
+    ///
+    /// Derive all the children of the path, the current key is considered as "m"
+    ///
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<ExtendedPrivateKey, BIP32Error> {
+        path.as_slice()
+            .iter()
+            .try_fold(self.clone(), |key, child_number| key.derive_child(*child_number))
+    }
+
+    ///
+    /// Get the associated extended public key (N function)
+    ///
+    pub fn to_extended_public_key(&self) -> ExtendedPublicKey {
+        ExtendedPublicKey {
+            depth: self.depth,
+            parent_fingerprint: self.parent_fingerprint,
+            child_number: self.child_number,
+            chain_code: self.chain_code,
+            public_key: self.private_key.public_key(),
+        }
+    }
+
+    ///
+    /// First 4 bytes of the public key identifier
+    ///
+    pub fn fingerprint(&self) -> [u8; 4] {
+        fingerprint(&self.public_key_bytes())
+    }
+
+    ///
+    /// Get the 32 bytes private key
+    ///
+    pub fn private_key(&self) -> Bytes {
+        Bytes::new(self.private_key.to_bytes().to_vec())
+    }
+
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    pub fn child_number(&self) -> ChildNumber {
+        self.child_number
+    }
+
+    fn public_key_bytes(&self) -> Vec<u8> {
+        self.private_key.public_key().to_encoded_point(true).as_bytes().to_vec()
+    }
+}
+
+impl Drop for ExtendedPrivateKey {
+    fn drop(&mut self) {
+        self.chain_code.zeroize();
+    }
+}
+
+impl PartialEq for ExtendedPrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.depth == other.depth
+            && self.parent_fingerprint == other.parent_fingerprint
+            && self.child_number == other.child_number
+            && self.chain_code == other.chain_code
+            && self.private_key == other.private_key
+    }
+}
+
+///
+/// Never display the keys, to avoid leaking them into the logs
+///
+impl Debug for ExtendedPrivateKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExtendedPrivateKey")
+            .field("depth", &self.depth)
+            .field("parent_fingerprint", &hex::encode(self.parent_fingerprint))
+            .field("child_number", &self.child_number)
+            .finish_non_exhaustive()
+    }
+}
+
+///
+/// Serialize to Base58Check "xprv..."
+///
+impl Display for ExtendedPrivateKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut key = Zeroizing::new(Vec::with_capacity(33));
+        key.push(0);
+        key.extend_from_slice(&self.private_key.to_bytes());
+
+        write!(
+            f,
+            "{}",
+            encode_extended_key(
+                XPRV_VERSION,
+                self.depth,
+                self.parent_fingerprint,
+                self.child_number,
+                &self.chain_code,
+                &key
+            )
+            .as_str()
+        )
+    }
+}
+
+impl FromStr for ExtendedPrivateKey {
+    type Err = BIP32Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let data = decode_extended_key(value, XPRV_VERSION)?;
+
+        // Private key is prefixed by 0x00
+        if data[45] != 0 {
+            return Err(BIP32Error::InvalidExtendedKey(String::from(
+                "invalid private key prefix",
+            )));
+        }
+
+        Ok(ExtendedPrivateKey {
+            depth: data[4],
+            parent_fingerprint: data[5..9].try_into().unwrap(),
+            child_number: ChildNumber::from_index(u32::from_be_bytes(data[9..13].try_into().unwrap())),
+            chain_code: data[13..45].try_into().unwrap(),
+            private_key: SecretKey::from_slice(&data[46..78])
+                .map_err(|_| BIP32Error::InvalidExtendedKey(String::from("invalid private key")))?,
+        })
+    }
+}
+
+///
+/// BIP32 extended public key (xpub)
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtendedPublicKey {
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: ChildNumber,
+    chain_code: [u8; 32],
+    public_key: PublicKey,
+}
+
+impl ExtendedPublicKey {
+    ///
+    /// Derive the child public key (CKDpub), only available for non hardened child
+    ///
+    pub fn derive_child(&self, child_number: ChildNumber) -> Result<ExtendedPublicKey, BIP32Error> {
+        child_number.check()?;
+        if child_number.is_hardened() {
+            return Err(BIP32Error::HardenedDerivationFromPublicKey);
+        }
+
+        let mut data = self.public_key_bytes();
+        data.extend_from_slice(&child_number.to_index().to_be_bytes());
+
+        let (tweak, chain_code) = hmac_sha512_split(&self.chain_code, &data)?;
+        let tweak = parse_scalar(&tweak)?;
+
+        // Ki = point(parse256(IL)) + Kpar, invalid if the result is the point at infinity
+        let point = ProjectivePoint::GENERATOR * tweak + self.public_key.to_projective();
+        let public_key = PublicKey::from_affine(point.to_affine())
+            .map_err(|_| BIP32Error::InvalidPublicKey)?;
+
+        Ok(ExtendedPublicKey {
+            depth: self.depth.checked_add(1).ok_or(BIP32Error::MaxDepthExceeded)?,
+            parent_fingerprint: self.fingerprint(),
+            child_number,
+            chain_code,
+            public_key,
+        })
+    }
+
+    ///
+    /// Derive all the children of the path, the current key is considered as "m"
+    ///
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<ExtendedPublicKey, BIP32Error> {
+        path.as_slice()
+            .iter()
+            .try_fold(self.clone(), |key, child_number| key.derive_child(*child_number))
+    }
+
+    ///
+    /// First 4 bytes of the public key identifier
+    ///
+    pub fn fingerprint(&self) -> [u8; 4] {
+        fingerprint(&self.public_key_bytes())
+    }
+
+    ///
+    /// Get the 33 bytes compressed public key
+    ///
+    pub fn public_key_bytes(&self) -> Vec<u8> {
+        self.public_key.to_encoded_point(true).as_bytes().to_vec()
+    }
+
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    pub fn child_number(&self) -> ChildNumber {
+        self.child_number
+    }
+}
+
+///
+/// Serialize to Base58Check "xpub..."
+///
+impl Display for ExtendedPublicKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            encode_extended_key(
+                XPUB_VERSION,
+                self.depth,
+                self.parent_fingerprint,
+                self.child_number,
+                &self.chain_code,
+                &self.public_key_bytes()
+            )
+            .as_str()
+        )
+    }
+}
+
+impl FromStr for ExtendedPublicKey {
+    type Err = BIP32Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let data = decode_extended_key(value, XPUB_VERSION)?;
+
+        // Only compressed public keys are allowed
+        if data[45] != 2 && data[45] != 3 {
+            return Err(BIP32Error::InvalidExtendedKey(String::from(
+                "invalid public key prefix",
+            )));
+        }
+
+        Ok(ExtendedPublicKey {
+            depth: data[4],
+            parent_fingerprint: data[5..9].try_into().unwrap(),
+            child_number: ChildNumber::from_index(u32::from_be_bytes(data[9..13].try_into().unwrap())),
+            chain_code: data[13..45].try_into().unwrap(),
+            public_key: PublicKey::from_sec1_bytes(&data[45..78])
+                .map_err(|_| BIP32Error::InvalidExtendedKey(String::from("invalid public key")))?,
+        })
+    }
+}
+
+///
+/// Compute HMAC-SHA512 and split the result into IL and IR
+///
+pub(crate) fn hmac_sha512_split(
+    key: &[u8],
+    data: &[u8],
+) -> Result<(Zeroizing<[u8; 32]>, [u8; 32]), BIP32Error> {
+    let mut hmac =
+        Hmac::<Sha512>::new_from_slice(key).map_err(|e| BIP32Error::HmacError(e.to_string()))?;
+    hmac.update(data);
+    let result = Zeroizing::new(hmac.finalize().into_bytes());
+
+    let mut left = Zeroizing::new([0u8; 32]);
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&result[..32]);
+    right.copy_from_slice(&result[32..]);
+
+    Ok((left, right))
+}
+
+///
+/// parse256(IL), invalid if IL >= n
+///
+fn parse_scalar(bytes: &[u8; 32]) -> Result<Scalar, BIP32Error> {
+    Option::<Scalar>::from(Scalar::from_repr((*bytes).into())).ok_or(BIP32Error::InvalidPrivateKey)
+}
+
+///
+/// First 4 bytes of the public key identifier
+///
+pub(crate) fn fingerprint(public_key: &[u8]) -> [u8; 4] {
+    let identifier = hash160(public_key);
+    [identifier[0], identifier[1], identifier[2], identifier[3]]
+}
+
+///
+/// RIPEMD160(SHA256(public key)), the public key identifier
+///
+pub(crate) fn hash160(public_key: &[u8]) -> [u8; 20] {
+    Ripemd160::digest(utils::sha256(public_key)).into()
+}
+
+///
+/// Serialize the extended key
+/// version (4) || depth (1) || parent fingerprint (4) || child number (4) || chain code (32) || key (33)
+///
+fn encode_extended_key(
+    version: [u8; 4],
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: ChildNumber,
+    chain_code: &[u8; 32],
+    key: &[u8],
+) -> Zeroizing<String> {
+    let mut data = Zeroizing::new(Vec::with_capacity(EXTENDED_KEY_LEN));
+    data.extend_from_slice(&version);
+    data.push(depth);
+    data.extend_from_slice(&parent_fingerprint);
+    data.extend_from_slice(&child_number.to_index().to_be_bytes());
+    data.extend_from_slice(chain_code);
+    data.extend_from_slice(key);
+
+    Zeroizing::new(bs58::encode(data.as_slice()).with_check().into_string())
+}
+
+///
+/// Decode the Base58Check extended key and check the version and the header
+///
+fn decode_extended_key(value: &str, version: [u8; 4]) -> Result<Zeroizing<Vec<u8>>, BIP32Error> {
+    let data = Zeroizing::new(
+        bs58::decode(value)
+            .with_check(None)
+            .into_vec()
+            .map_err(|e| match e {
+                bs58::decode::Error::InvalidChecksum { .. } => BIP32Error::InvalidExtendedKeyChecksum,
+                e => BIP32Error::InvalidExtendedKey(e.to_string()),
+            })?,
+    );
+
+    if data.len() != EXTENDED_KEY_LEN {
+        return Err(BIP32Error::InvalidExtendedKey(format!(
+            "invalid length {}",
+            data.len()
+        )));
+    }
+
+    if data[..4] != version {
+        return Err(BIP32Error::InvalidExtendedKey(String::from("invalid version")));
+    }
+
+    // The master key has no parent and is not a child
+    if data[4] == 0 && (data[5..9] != [0; 4] || data[9..13] != [0; 4]) {
+        return Err(BIP32Error::InvalidExtendedKey(String::from(
+            "zero depth with non-zero parent fingerprint or index",
+        )));
+    }
+
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::error::BIP32Error;
+
+    use super::{ChildNumber, DerivationPath};
+
+    ///
+    /// Parse derivation path with the different hardened notations
+    ///
+    #[test]
+    fn parse_derivation_path() {
+        let path = DerivationPath::from_str("m/44'/0h/0H/0/2147483647").unwrap();
+
+        assert_eq!(
+            path.as_slice(),
+            &[
+                ChildNumber::Hardened(44),
+                ChildNumber::Hardened(0),
+                ChildNumber::Hardened(0),
+                ChildNumber::Normal(0),
+                ChildNumber::Normal(2147483647),
+            ]
+        );
+        assert_eq!(path.to_string(), "m/44'/0'/0'/0/2147483647");
+        assert_eq!(DerivationPath::from_str("m").unwrap().as_slice(), &[]);
+    }
+
+    ///
+    /// Invalid derivation path should err
+    ///
+    #[test]
+    fn parse_invalid_derivation_path_should_err() {
+        assert_eq!(
+            DerivationPath::from_str("44'/0'"),
+            Err(BIP32Error::InvalidDerivationPath("44'/0'".to_owned()))
+        );
+        assert_eq!(
+            DerivationPath::from_str("m/a'"),
+            Err(BIP32Error::InvalidDerivationPath("a'".to_owned()))
+        );
+        assert_eq!(
+            DerivationPath::from_str("m/0/"),
+            Err(BIP32Error::InvalidDerivationPath("".to_owned()))
+        );
+        assert_eq!(
+            DerivationPath::from_str("m/2147483648"),
+            Err(BIP32Error::InvalidChildNumber(2147483648))
+        );
+    }
+
+    ///
+    /// Child number index with the hardened offset
+    ///
+    #[test]
+    fn child_number_index() {
+        assert_eq!(ChildNumber::Hardened(0).to_index(), 0x80000000);
+        assert_eq!(ChildNumber::Normal(1).to_index(), 1);
+        assert_eq!(ChildNumber::from_index(0x80000002), ChildNumber::Hardened(2));
+        assert_eq!(ChildNumber::from_index(2), ChildNumber::Normal(2));
+    }
+}
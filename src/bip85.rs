@@ -0,0 +1,192 @@
+use zeroize::Zeroizing;
+
+use crate::{
+    bip32::{self, ChildNumber, DerivationPath, ExtendedPrivateKey},
+    entropy::{Bytes, Entropy, EntropySize},
+    error::BIP32Error,
+    language::{Language, WordsCount},
+    mnemonic::Mnemonic,
+};
+
+/* Config */
+const BIP85_PURPOSE: u32 = 83696968;
+const ENTROPY_HMAC: &[u8] = b"bip-entropy-from-k";
+const APPLICATION_BIP39: u32 = 39;
+const APPLICATION_HEX: u32 = 128169;
+const APPLICATION_WIF: u32 = 2;
+const APPLICATION_XPRV: u32 = 32;
+const MIN_HEX_LEN: u32 = 16;
+const MAX_HEX_LEN: u32 = 64;
+const WIF_VERSION: u8 = 0x80;
+const WIF_COMPRESSED: u8 = 0x01;
+
+///
+/// BIP85 deterministic entropy from BIP32 keychains
+/// From documentation (<https://github.com/bitcoin/bips/blob/master/bip-0085.mediawiki>)
+/// Derive the 64 bytes entropy of the path (must be fully hardened)
+///     k = derived private key at the path
+///     entropy = HMAC-SHA512(Key = "bip-entropy-from-k", Data = k)
+///
+pub fn derive_entropy(
+    root: &ExtendedPrivateKey,
+    path: &DerivationPath,
+) -> Result<Bytes, BIP32Error> {
+    if let Some(child_number) = path
+        .as_slice()
+        .iter()
+        .find(|child_number| !child_number.is_hardened())
+    {
+        return Err(BIP32Error::NonHardenedDerivation(child_number.to_index()));
+    }
+
+    let private_key = root.derive_path(path)?.private_key();
+    let (left, right) = bip32::hmac_sha512_split(ENTROPY_HMAC, private_key.as_ref())?;
+
+    let mut entropy = Vec::with_capacity(64);
+    entropy.extend_from_slice(left.as_ref());
+    entropy.extend_from_slice(&right);
+
+    Ok(Bytes::new(entropy))
+}
+
+///
+/// BIP39 application, derive a child mnemonic at m/83696968'/39'/{language}'/{words}'/{index}'
+///
+pub fn to_mnemonic(
+    root: &ExtendedPrivateKey,
+    lang: Language,
+    nb_words: WordsCount,
+    index: u32,
+) -> Result<Mnemonic, BIP32Error> {
+    let path = application_path(&[
+        APPLICATION_BIP39,
+        language_code(&lang)?,
+        nb_words.nb_words() as u32,
+        index,
+    ]);
+    let entropy_size = EntropySize::from(nb_words);
+    let entropy = derive_entropy(root, &path)?.take(entropy_size.nb_bytes());
+
+    Mnemonic::from_entropy(Entropy::from_bytes_vec(entropy.into_vec())?, lang)
+}
+
+///
+/// HEX application, derive between 16 and 64 bytes at m/83696968'/128169'/{num_bytes}'/{index}'
+///
+pub fn to_hex(root: &ExtendedPrivateKey, nb_bytes: u32, index: u32) -> Result<Bytes, BIP32Error> {
+    if !(MIN_HEX_LEN..=MAX_HEX_LEN).contains(&nb_bytes) {
+        return Err(BIP32Error::InvalidBIP85Parameter(format!(
+            "the number of bytes ({}) should be between {} and {}",
+            nb_bytes, MIN_HEX_LEN, MAX_HEX_LEN
+        )));
+    }
+
+    let path = application_path(&[APPLICATION_HEX, nb_bytes, index]);
+
+    Ok(derive_entropy(root, &path)?.take(nb_bytes as usize))
+}
+
+///
+/// WIF application, derive a compressed mainnet private key at m/83696968'/2'/{index}'
+///
+pub fn to_wif(root: &ExtendedPrivateKey, index: u32) -> Result<Zeroizing<String>, BIP32Error> {
+    let path = application_path(&[APPLICATION_WIF, index]);
+    let entropy = derive_entropy(root, &path)?;
+
+    let mut data = Zeroizing::new(Vec::with_capacity(34));
+    data.push(WIF_VERSION);
+    data.extend_from_slice(&entropy.as_ref()[..32]);
+    data.push(WIF_COMPRESSED);
+
+    Ok(Zeroizing::new(
+        bs58::encode(data.as_slice()).with_check().into_string(),
+    ))
+}
+
+///
+/// XPRV application, derive a master extended private key at m/83696968'/32'/{index}'
+/// The first 32 bytes of the entropy are the chain code, the last 32 bytes are the private key
+///
+pub fn to_xprv(root: &ExtendedPrivateKey, index: u32) -> Result<ExtendedPrivateKey, BIP32Error> {
+    let path = application_path(&[APPLICATION_XPRV, index]);
+    let entropy = derive_entropy(root, &path)?;
+
+    let mut chain_code = [0u8; 32];
+    chain_code.copy_from_slice(&entropy.as_ref()[..32]);
+
+    ExtendedPrivateKey::from_parts(chain_code, &entropy.as_ref()[32..])
+}
+
+///
+/// Build the fully hardened path m/83696968'/{application}'/...
+///
+fn application_path(indexes: &[u32]) -> DerivationPath {
+    DerivationPath::new(
+        [BIP85_PURPOSE]
+            .iter()
+            .chain(indexes.iter())
+            .map(|index| ChildNumber::Hardened(*index))
+            .collect(),
+    )
+}
+
+///
+/// Language code of the BIP39 application
+///
+fn language_code(lang: &Language) -> Result<u32, BIP32Error> {
+    match lang {
+        Language::English => Ok(0),
+        Language::Japanese => Ok(1),
+        Language::Korean => Ok(2),
+        Language::Spanish => Ok(3),
+        Language::ChineseSimplified => Ok(4),
+        Language::ChineseTraditional => Ok(5),
+        Language::French => Ok(6),
+        Language::Italian => Ok(7),
+        Language::Czech => Ok(8),
+        Language::Portugese => Ok(9),
+        Language::Custom(_) => Err(BIP32Error::InvalidBIP85Parameter(String::from(
+            "custom words lists have no language code",
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{
+        bip32::{ChildNumber, DerivationPath, ExtendedPrivateKey},
+        error::BIP32Error,
+    };
+
+    use super::{application_path, derive_entropy};
+
+    ///
+    /// Application path are fully hardened
+    ///
+    #[test]
+    fn build_application_path() {
+        assert_eq!(
+            application_path(&[39, 0, 12, 0]),
+            DerivationPath::from_str("m/83696968'/39'/0'/12'/0'").unwrap()
+        );
+    }
+
+    ///
+    /// Non hardened path should err
+    ///
+    #[test]
+    fn derive_entropy_from_non_hardened_path_should_err() {
+        let root = ExtendedPrivateKey::new_master(&[0; 16]).unwrap();
+        let path = DerivationPath::new(vec![
+            ChildNumber::Hardened(83696968),
+            ChildNumber::Normal(0),
+        ]);
+
+        assert_eq!(
+            derive_entropy(&root, &path),
+            Err(BIP32Error::NonHardenedDerivation(0))
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::error::BIP32Error;
+
+/* Config */
+const RANKS: &str = "A23456789TJQK";
+const SUITS: &str = "CDHS";
+pub const DECK_SIZE: usize = 52;
+
+///
+/// Suit of a standard 52 cards deck
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+}
+
+///
+/// Card of a standard 52 cards deck, the rank is between 1 (ace) and 13 (king)
+/// Written as the rank ("A", "2".."9", "T", "J", "Q", "K") followed by the suit ("C", "D", "H", "S"),
+/// as "AS" for the ace of spades or "TD" for the ten of diamonds
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Card {
+    rank: u8,
+    suit: Suit,
+}
+
+impl Card {
+    ///
+    /// Create a card from its rank (1 to 13) and its suit
+    ///
+    pub fn new(rank: u8, suit: Suit) -> Result<Card, BIP32Error> {
+        if !(1..=RANKS.len() as u8).contains(&rank) {
+            return Err(BIP32Error::InvalidEntropySource(format!(
+                "the card rank {} should be between 1 and {}",
+                rank,
+                RANKS.len()
+            )));
+        }
+
+        Ok(Card { rank, suit })
+    }
+
+    pub fn rank(&self) -> u8 {
+        self.rank
+    }
+
+    pub fn suit(&self) -> Suit {
+        self.suit
+    }
+}
+
+impl Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let suit = match self.suit {
+            Suit::Clubs => 'C',
+            Suit::Diamonds => 'D',
+            Suit::Hearts => 'H',
+            Suit::Spades => 'S',
+        };
+
+        write!(
+            f,
+            "{}{}",
+            &RANKS[self.rank as usize - 1..self.rank as usize],
+            suit
+        )
+    }
+}
+
+///
+/// Parse a card as "AS", "TD" or "10D", case insensitive
+///
+impl FromStr for Card {
+    type Err = BIP32Error;
+
+    fn from_str(card: &str) -> Result<Self, Self::Err> {
+        let invalid_card =
+            || BIP32Error::InvalidEntropySource(format!("the card '{}' is not valid", card));
+
+        let card_upper = card.trim().to_uppercase();
+        let (rank, suit) = card_upper
+            .split_at_checked(card_upper.len().saturating_sub(1))
+            .ok_or_else(invalid_card)?;
+        let rank = match rank {
+            "10" => "T",
+            rank => rank,
+        };
+
+        let rank = match rank.len() {
+            1 => RANKS.find(rank).ok_or_else(invalid_card)? as u8 + 1,
+            _ => return Err(invalid_card()),
+        };
+        let suit = match SUITS.find(suit) {
+            Some(0) => Suit::Clubs,
+            Some(1) => Suit::Diamonds,
+            Some(2) => Suit::Hearts,
+            Some(3) => Suit::Spades,
+            _ => return Err(invalid_card()),
+        };
+
+        Card::new(rank, suit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::error::BIP32Error;
+
+    use super::{Card, Suit};
+
+    ///
+    /// Cards are parsed and displayed with the rank followed by the suit
+    ///
+    #[test]
+    fn parse_and_display_card() {
+        let list = vec![
+            ("AS", Card::new(1, Suit::Spades).unwrap(), "AS"),
+            ("td", Card::new(10, Suit::Diamonds).unwrap(), "TD"),
+            ("10H", Card::new(10, Suit::Hearts).unwrap(), "TH"),
+            ("KC", Card::new(13, Suit::Clubs).unwrap(), "KC"),
+            ("7h", Card::new(7, Suit::Hearts).unwrap(), "7H"),
+        ];
+
+        for (input, card, display) in list {
+            assert_eq!(Card::from_str(input).unwrap(), card);
+            assert_eq!(card.to_string(), display);
+        }
+    }
+
+    ///
+    /// Unknown ranks and suits should err
+    ///
+    #[test]
+    fn parse_invalid_card_should_err() {
+        for card in ["", "A", "1S", "11H", "ZS", "AX", "AS2"] {
+            assert!(matches!(
+                Card::from_str(card),
+                Err(BIP32Error::InvalidEntropySource(_))
+            ));
+        }
+        assert!(Card::new(0, Suit::Spades).is_err());
+        assert!(Card::new(14, Suit::Spades).is_err());
+    }
+}
@@ -1,4 +1,15 @@
-use crate::{error::BIP32Error, language::WordsCount, utils, ENTROPY_MULTIPLE, NB_BITS_IN_BYTE};
+use std::fmt::Debug;
+use std::collections::HashSet;
+
+use rand::{rngs::OsRng, CryptoRng, RngCore};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::{
+    card::{Card, DECK_SIZE},
+    error::BIP32Error,
+    language::WordsCount,
+    utils, ENTROPY_MULTIPLE, NB_BITS_IN_BYTE,
+};
 
 ///
 /// The entropy bits number
@@ -73,7 +84,10 @@ impl From<Entropy> for EntropySize {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Bytes holding secret material (entropy, seed...), wiped from memory when dropped
+///
+#[derive(Clone, PartialEq, Zeroize, ZeroizeOnDrop)]
 pub struct Bytes {
     val: Vec<u8>,
 }
@@ -90,12 +104,24 @@ impl Bytes {
         &self.val
     }
 
+    // Kept next to the AsRef implementation, removing it would break the callers of the inherent method
+    #[allow(clippy::should_implement_trait)]
+    pub fn as_ref(&self) -> &[u8] {
+        self.val.as_ref()
+    }
+
+    ///
+    /// Copy the bytes into a vector, the copy is not wiped when dropped, see `into_zeroizing_vec`
+    ///
     pub fn into_vec(&self) -> Vec<u8> {
         self.val.clone()
     }
 
-    pub fn as_ref(&self) -> &[u8] {
-        &self.val.as_ref()
+    ///
+    /// Move the bytes out without copying them, the vector is wiped when dropped
+    ///
+    pub fn into_zeroizing_vec(mut self) -> Zeroizing<Vec<u8>> {
+        Zeroizing::new(std::mem::take(&mut self.val))
     }
 
     pub fn to_hex(&self) -> String {
@@ -115,31 +141,42 @@ impl Bytes {
     }
 
     pub fn take(&self, count: usize) -> Bytes {
-        Bytes::new(self.val.clone().into_iter().take(count).collect())
+        Bytes::new(self.val[..count.min(self.val.len())].to_vec())
     }
 
     ///
     /// Concat two bytes vector
     /// 
     pub fn concat(&self, other: &mut Bytes) -> Bytes {
-        let mut res = self.val.clone();
+        let mut res = Vec::with_capacity(self.val.len() + other.val.len());
+        res.extend_from_slice(&self.val);
         res.append(&mut other.val);
         Bytes::new(res)
     }
 
     ///
-    /// Get bytes from hexadecimal
+    /// Get bytes from hexadecimal, the hexadecimal string is wiped after decoding
     /// 
-    pub fn from_hex(hex: String) -> Result<Bytes, BIP32Error> {
-        Ok(Bytes::new(
-            hex::decode(hex).map_err(|e| BIP32Error::HexError(e.to_string()))?,
-        ))
+    pub fn from_hex(mut hex: String) -> Result<Bytes, BIP32Error> {
+        let bytes = hex::decode(&hex).map_err(|e| BIP32Error::HexError(e.to_string()));
+        hex.zeroize();
+
+        Ok(Bytes::new(bytes?))
+    }
+}
+
+///
+/// Never display the bytes, to avoid leaking secrets into the logs
+///
+impl Debug for Bytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Bytes([REDACTED; {}])", self.val.len())
     }
 }
 
 impl AsRef<[u8]> for Bytes {
     fn as_ref(&self) -> &[u8] {
-        &self.val.as_ref()
+        self.val.as_ref()
     }
 }
 
@@ -154,7 +191,7 @@ impl From<Vec<u8>> for Bytes {
 /// Entropy (ENT) representation
 /// The allowed size of ENT is 128-256 bits and have to be a multiple of 32 bits
 /// 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Zeroize, ZeroizeOnDrop)]
 pub struct Entropy {
     pub entropy: Bytes,
 }
@@ -177,17 +214,186 @@ impl Entropy {
     }
 
     /// 
-    /// Generate a random entropy from the specific EntropySize selected
+    /// Generate a random entropy from the specific EntropySize selected, with the OS cryptographically secure RNG
     /// 
     pub fn generate(entropy_size: EntropySize) -> Entropy {
-        Entropy::from(entropy_size)
+        Entropy::generate_with_rng(entropy_size, &mut OsRng)
+    }
+
+    ///
+    /// Generate a random entropy with the given cryptographically secure RNG (HSM, deterministic RNG for the tests...)
+    ///
+    pub fn generate_with_rng<R: RngCore + CryptoRng + ?Sized>(
+        entropy_size: EntropySize,
+        rng: &mut R,
+    ) -> Entropy {
+        let mut entropy = vec![0u8; entropy_size.nb_bytes()];
+        rng.fill_bytes(&mut entropy);
+
+        Entropy {
+            entropy: Bytes::from(entropy),
+        }
+    }
+
+    ///
+    /// Create the entropy from physical dice rolls, each roll between 1 and the number of sides
+    /// As Coldcard and SeedSigner, the entropy is the SHA256 of the rolls written as digits ("6", "1", "3"...),
+    /// truncated to the entropy size (the rolls of dice with 10 sides or more are zero padded, "07", "12"...).
+    /// The minimum number of rolls is the one of these devices, the entropy size divided by the bits of a roll,
+    /// rounded to the nearest roll: 50 rolls of a 6 sides dice for 12 words and 99 rolls for 24 words, so the
+    /// same rolls give the same mnemonic on both sides (99 rolls only carry 255.9 bits, see `from_dice_rolls_strict`)
+    ///
+    pub fn from_dice_rolls(
+        rolls: &[u8],
+        sides: u8,
+        entropy_size: EntropySize,
+    ) -> Result<Entropy, BIP32Error> {
+        let bits_per_roll = Entropy::dice_bits_per_roll(rolls, sides)?;
+        let min_rolls = (entropy_size.nb_bits() as f64 / bits_per_roll).round() as usize;
+
+        Entropy::from_dice_rolls_with_minimum(rolls, sides, min_rolls, entropy_size)
+    }
+
+    ///
+    /// Create the entropy from physical dice rolls as `from_dice_rolls`, but the rolls must carry the full
+    /// entropy size: 50 rolls of a 6 sides dice for 12 words and 100 rolls for 24 words
+    ///
+    pub fn from_dice_rolls_strict(
+        rolls: &[u8],
+        sides: u8,
+        entropy_size: EntropySize,
+    ) -> Result<Entropy, BIP32Error> {
+        let bits_per_roll = Entropy::dice_bits_per_roll(rolls, sides)?;
+        let min_rolls = (entropy_size.nb_bits() as f64 / bits_per_roll).ceil() as usize;
+
+        Entropy::from_dice_rolls_with_minimum(rolls, sides, min_rolls, entropy_size)
+    }
+
+    ///
+    /// Check the rolls and get the entropy bits carried by a roll
+    ///
+    fn dice_bits_per_roll(rolls: &[u8], sides: u8) -> Result<f64, BIP32Error> {
+        if sides < 2 {
+            return Err(BIP32Error::InvalidEntropySource(format!(
+                "a dice should have at least 2 sides, not {}",
+                sides
+            )));
+        }
+        if let Some(roll) = rolls.iter().find(|roll| !(1..=sides).contains(*roll)) {
+            return Err(BIP32Error::InvalidEntropySource(format!(
+                "the roll {} should be between 1 and {}",
+                roll, sides
+            )));
+        }
+
+        Ok(f64::from(sides).log2())
+    }
+
+    fn from_dice_rolls_with_minimum(
+        rolls: &[u8],
+        sides: u8,
+        min_rolls: usize,
+        entropy_size: EntropySize,
+    ) -> Result<Entropy, BIP32Error> {
+        if rolls.len() < min_rolls {
+            return Err(BIP32Error::InsufficientEntropy(
+                (rolls.len() as f64 * f64::from(sides).log2()) as usize,
+                entropy_size.nb_bits(),
+            ));
+        }
+
+        let width = sides.to_string().len();
+        let record = Zeroizing::new(
+            rolls
+                .iter()
+                .map(|roll| format!("{:0width$}", roll, width = width))
+                .collect::<String>(),
+        );
+
+        Ok(Entropy::hash_physical_source(record.as_bytes(), entropy_size))
+    }
+
+    ///
+    /// Create the entropy from coin flips (true for heads, false for tails), one bit of entropy per flip
+    /// The entropy is the SHA256 of the flips written as "1" (heads) and "0" (tails), truncated to the entropy size
+    ///
+    pub fn from_coin_flips(flips: &[bool], entropy_size: EntropySize) -> Result<Entropy, BIP32Error> {
+        let record = Zeroizing::new(
+            flips
+                .iter()
+                .map(|flip| if *flip { '1' } else { '0' })
+                .collect::<String>(),
+        );
+
+        Entropy::from_physical_source(record.as_bytes(), flips.len() as f64, entropy_size)
+    }
+
+    ///
+    /// Create the entropy from cards drawn from a shuffled 52 cards deck, without replacement: each card can
+    /// only be drawn once and the cards carry log2(52! / (52 - n)!) bits. A full deck carries 225 bits, enough
+    /// for 21 words but not for 24 words (see `from_card_draws`).
+    /// The entropy is the SHA256 of the cards written as "AS", "TD"..., truncated to the entropy size
+    ///
+    pub fn from_card_deck(cards: &[Card], entropy_size: EntropySize) -> Result<Entropy, BIP32Error> {
+        let mut unique_cards = HashSet::new();
+        if let Some(card) = cards.iter().find(|card| !unique_cards.insert(*card)) {
+            return Err(BIP32Error::InvalidEntropySource(format!(
+                "the card {} is present several times in the shuffled deck",
+                card
+            )));
+        }
+
+        let nb_bits = (0..cards.len())
+            .map(|i| ((DECK_SIZE - i) as f64).log2())
+            .sum();
+        let record = Zeroizing::new(cards.iter().map(Card::to_string).collect::<String>());
+
+        Entropy::from_physical_source(record.as_bytes(), nb_bits, entropy_size)
+    }
+
+    ///
+    /// Create the entropy from cards drawn with replacement: the card is put back and the full deck is
+    /// shuffled again before each draw, so a card can be drawn several times. Each card carries log2(52) bits,
+    /// 45 draws are required for 24 words.
+    /// The entropy is the SHA256 of the cards written as "AS", "TD"..., truncated to the entropy size
+    ///
+    pub fn from_card_draws(cards: &[Card], entropy_size: EntropySize) -> Result<Entropy, BIP32Error> {
+        let nb_bits = cards.len() as f64 * (DECK_SIZE as f64).log2();
+        let record = Zeroizing::new(cards.iter().map(Card::to_string).collect::<String>());
+
+        Entropy::from_physical_source(record.as_bytes(), nb_bits, entropy_size)
+    }
+
+    ///
+    /// Hash the record of a physical source, the hash removes the bias of the conversion to bits
+    /// Refuse the record if it doesn't carry enough bits of entropy
+    ///
+    fn from_physical_source(
+        record: &[u8],
+        nb_bits: f64,
+        entropy_size: EntropySize,
+    ) -> Result<Entropy, BIP32Error> {
+        if nb_bits < entropy_size.nb_bits() as f64 {
+            return Err(BIP32Error::InsufficientEntropy(
+                nb_bits as usize,
+                entropy_size.nb_bits(),
+            ));
+        }
+
+        Ok(Entropy::hash_physical_source(record, entropy_size))
+    }
+
+    fn hash_physical_source(record: &[u8], entropy_size: EntropySize) -> Entropy {
+        Entropy {
+            entropy: Bytes::new(utils::sha256(record)).take(entropy_size.nb_bytes()),
+        }
     }
 
     ///
     /// Entropy to EntropySize enum
     /// 
     pub fn get_entropy_size(&self) -> EntropySize {
-        self.clone().into()
+        EntropySize::from(self.entropy.nb_bits())
     }
 
     /// 
@@ -204,6 +410,13 @@ impl Entropy {
         self.checksum().nb_bytes() / ENTROPY_MULTIPLE
     }
 
+    ///
+    /// Get the number of checksum bits used by the mnemonic (ENT / 32)
+    ///
+    pub fn checksum_nb_bits(&self) -> usize {
+        self.entropy.nb_bits() / ENTROPY_MULTIPLE
+    }
+
     ///
     /// Concat current entropy with checksum
     /// 
@@ -219,11 +432,12 @@ impl Entropy {
 impl TryFrom<Vec<u8>> for Entropy {
     type Error = BIP32Error;
 
-    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+    fn try_from(mut value: Vec<u8>) -> Result<Self, Self::Error> {
         if value.len() * NB_BITS_IN_BYTE < 128
             || value.len() * NB_BITS_IN_BYTE > 256
-            || value.len() * NB_BITS_IN_BYTE % ENTROPY_MULTIPLE != 0
+            || !(value.len() * NB_BITS_IN_BYTE).is_multiple_of(ENTROPY_MULTIPLE)
         {
+            value.zeroize();
             return Err(BIP32Error::InvalidEntropy);
         }
 
@@ -238,13 +452,7 @@ impl TryFrom<Vec<u8>> for Entropy {
 /// 
 impl From<EntropySize> for Entropy {
     fn from(entropy_size: EntropySize) -> Self {
-        Entropy {
-            entropy: Bytes::from(
-                (0..entropy_size.nb_bytes())
-                    .map(|_| rand::random::<u8>())
-                    .collect::<Vec<u8>>(),
-            ),
-        }
+        Entropy::generate(entropy_size)
     }
 }
 
@@ -264,7 +472,7 @@ impl Default for Entropy {
 #[cfg(test)]
 mod test {
     use crate::{
-        entropy::{Entropy, EntropySize},
+        entropy::{Bytes, Entropy, EntropySize},
         error::BIP32Error,
         language::WordsCount,
         NB_BITS_IN_BYTE,
@@ -272,7 +480,7 @@ mod test {
 
     // Private function to create default entropy (only 0) from entropy size
     fn generate_default_entropy(nb_bytes: usize) -> Vec<u8> {
-        (0..nb_bytes).map(|_| 0 as u8).collect::<Vec<u8>>()
+        (0..nb_bytes).map(|_| 0_u8).collect::<Vec<u8>>()
     }
 
     ///
@@ -299,27 +507,27 @@ mod test {
 
         assert_eq!(
             default_128_entropy.entropy.as_vec(),
-            &vec![0 as u8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+            &vec![0_u8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
         );
         assert_eq!(
             default_160_entropy.entropy.as_vec(),
-            &vec![0 as u8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+            &vec![0_u8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
         );
         assert_eq!(
             default_192_entropy.entropy.as_vec(),
-            &vec![0 as u8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+            &vec![0_u8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
         );
         assert_eq!(
             default_224_entropy.entropy.as_vec(),
             &vec![
-                0 as u8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0_u8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
                 0, 0
             ]
         );
         assert_eq!(
             default_256_entropy.entropy.as_vec(),
             &vec![
-                0 as u8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0_u8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
                 0, 0, 0, 0, 0, 0
             ]
         );
@@ -345,7 +553,7 @@ mod test {
         let entropy = Entropy::from_hex("00000000000000000000000000000000".to_owned()).unwrap();
         assert_eq!(
             entropy.entropy.val,
-            vec![0 as u8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+            vec![0_u8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
         );
         assert_eq!(entropy.get_entropy_size(), EntropySize::Bits128);
 
@@ -435,10 +643,63 @@ mod test {
     #[test]
     fn create_entropy_from_default_and_calc_checksum() {
         // Default entropy (all bytes = 0)
-        let mut entropy = Entropy::from_bytes_vec(Entropy::default().entropy.val).unwrap();
+        let mut entropy = Entropy::from_bytes_vec(Entropy::default().entropy.into_vec()).unwrap();
         let checksum = entropy.concat_with_checksum();
 
         assert_eq!(entropy.entropy.nb_bits(), 256);
         assert_eq!(checksum.nb_bits(), 264);
     }
+
+    ///
+    /// Secrets should never be displayed
+    ///
+    #[test]
+    fn entropy_debug_is_redacted() {
+        let entropy = Entropy::from_hex("ffffffffffffffffffffffffffffffff".to_owned()).unwrap();
+
+        assert_eq!(
+            format!("{:?}", entropy),
+            "Entropy { entropy: Bytes([REDACTED; 16]) }"
+        );
+    }
+
+    ///
+    /// Take and concat should not change the bytes
+    ///
+    #[test]
+    fn bytes_take_and_concat() {
+        let bytes = Bytes::new(vec![1, 2, 3, 4]);
+
+        assert_eq!(bytes.take(2).as_vec(), &vec![1, 2]);
+        assert_eq!(bytes.take(10).as_vec(), &vec![1, 2, 3, 4]);
+        assert_eq!(bytes.concat(&mut Bytes::new(vec![5])).as_vec(), &vec![1, 2, 3, 4, 5]);
+    }
+
+    ///
+    /// The copy and the moved out vector should hold the same bytes
+    ///
+    #[test]
+    fn bytes_into_vec_and_zeroizing_vec() {
+        let bytes = Bytes::new(vec![1, 2, 3, 4]);
+
+        assert_eq!(bytes.into_vec(), vec![1, 2, 3, 4]);
+        assert_eq!(*bytes.into_zeroizing_vec(), vec![1, 2, 3, 4]);
+    }
+
+    ///
+    /// The same RNG state should generate the same entropy
+    ///
+    #[test]
+    fn generate_entropy_with_rng() {
+        use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+
+        let entropy = Entropy::generate_with_rng(EntropySize::Bits128, &mut ChaCha20Rng::seed_from_u64(42));
+
+        assert_eq!(entropy.entropy.nb_bits(), 128);
+        assert_eq!(
+            Entropy::generate_with_rng(EntropySize::Bits128, &mut ChaCha20Rng::seed_from_u64(42)),
+            entropy
+        );
+        assert_ne!(Entropy::generate(EntropySize::Bits128), entropy);
+    }
 }
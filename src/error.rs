@@ -1,5 +1,7 @@
 use std::fmt::Display;
 
+use crate::language::Language;
+
 ///
 /// Represent all errors that can happen during the program
 /// 
@@ -8,9 +10,6 @@ pub enum BIP32Error {
     /// More than 128 bits, less than 256 bits and be a multiple of 32
     InvalidEntropy,
 
-    /// Error when reading the language words
-    ReadFile(String),
-
     /// Count number doesn't match
     InvalidWordsCount(usize),
 
@@ -22,17 +21,144 @@ pub enum BIP32Error {
 
     /// Error when trying to encode or decode from / hex
     HexError(String),
+
+    /// The word is not part of the selected language words list
+    UnknownWord(String),
+
+    /// The checksum computed from the entropy doesn't match the mnemonic checksum
+    InvalidChecksum,
+
+    /// No language words list contains all the words of the phrase
+    UnknownLanguage,
+
+    /// Several languages words list contain all the words of the phrase
+    AmbiguousLanguage(Vec<Language>),
+
+    /// The abbreviation is the beginning of several words of the words list
+    AmbiguousWord(String, Vec<String>),
+
+    /// The word can't be part of a words list
+    InvalidWord(String),
+
+    /// The word is present several times in the words list
+    DuplicateWord(String),
+
+    /// The seed must be between 128 and 512 bits
+    InvalidSeedLength(usize),
+
+    /// The derivation path is not like "m/44'/0'/0'/0/0"
+    InvalidDerivationPath(String),
+
+    /// The child index must be lower than 2^31
+    InvalidChildNumber(u32),
+
+    /// The derived private key is not valid (0 or greater than the curve order)
+    InvalidPrivateKey,
+
+    /// The derived public key is not valid (point at infinity)
+    InvalidPublicKey,
+
+    /// Hardened child can't be derived from an extended public key
+    HardenedDerivationFromPublicKey,
+
+    /// Only hardened derivation is supported (SLIP-0010 ed25519, BIP85)
+    NonHardenedDerivation(u32),
+
+    /// More than 255 derivations
+    MaxDepthExceeded,
+
+    /// Error when trying to decode an extended key
+    InvalidExtendedKey(String),
+
+    /// The Base58Check checksum of the extended key is not valid
+    InvalidExtendedKeyChecksum,
+
+    /// Error when computing HMAC
+    HmacError(String),
+
+    /// The SLIP-39 share checksum is not valid
+    InvalidShareChecksum,
+
+    /// The SLIP-39 share mnemonic can't be decoded
+    InvalidShare(String),
+
+    /// The SLIP-39 shares can't be combined together
+    InvalidShareSet(String),
+
+    /// The digest of the recovered secret doesn't match, the shares are not consistent
+    InvalidShareDigest,
+
+    /// The thresholds, the counts or the iteration exponent can't be used to split the secret
+    InvalidSharingParameters(String),
+
+    /// The SLIP-39 passphrase must only contain printable ASCII characters
+    InvalidPassphrase,
+
+    /// The master secret must be at least 128 bits and its length must be even
+    InvalidMasterSecretLength(usize),
+
+    /// The BIP85 application can't be derived with this parameter
+    InvalidBIP85Parameter(String),
+
+    /// The dice rolls, coin flips or cards can't be used as entropy source
+    InvalidEntropySource(String),
+
+    /// The dice rolls, coin flips or cards don't carry enough bits of entropy for the requested size
+    InsufficientEntropy(usize, usize),
+
+    /// The recovery can only search one or two unknown words
+    TooManyUnknownWords(usize),
+
+    /// The passphrase mask can't be parsed (unknown "?x" placeholder)
+    InvalidPassphraseMask(String),
+
+    /// The recovery has been cancelled before the end of the search
+    RecoveryCancelled,
+
+    /// The phrase already has a valid checksum, there's nothing to recover
+    ChecksumAlreadyValid,
 }
 
 impl BIP32Error {
     pub fn message(&self) -> String {
         match self {
             BIP32Error::InvalidEntropy => String::from("Entropy not valid. It should be more than 128 bits, less than 256 bits and be a multiple of 32"),
-            BIP32Error::ReadFile(error) => String::from(format!("Error when reading file : {}", error)),
-            BIP32Error::InvalidWordsCount(nb) => String::from(format!("The words count ({}) is not valid", nb)),
-            BIP32Error::BitReader(error) => String::from(format!("Error while reading bit : {}", error)),
-            BIP32Error::WordNotFound(index) => String::from(format!("No word found at selected index {}", index)),
-            BIP32Error::HexError(error) => String::from(format!("Error when trying to encode or decode hexadecimal {}", error)),
+            BIP32Error::InvalidWordsCount(nb) => format!("The words count ({}) is not valid", nb),
+            BIP32Error::BitReader(error) => format!("Error while reading bit : {}", error),
+            BIP32Error::WordNotFound(index) => format!("No word found at selected index {}", index),
+            BIP32Error::HexError(error) => format!("Error when trying to encode or decode hexadecimal {}", error),
+            BIP32Error::UnknownWord(word) => format!("The word '{}' is not in the words list", word),
+            BIP32Error::InvalidChecksum => String::from("The mnemonic checksum is not valid"),
+            BIP32Error::UnknownLanguage => String::from("No language found for the given phrase"),
+            BIP32Error::AmbiguousLanguage(candidates) => format!("The phrase matches several languages : {:?}", candidates),
+            BIP32Error::AmbiguousWord(abbreviation, candidates) => format!("The abbreviation '{}' matches several words : {:?}", abbreviation, candidates),
+            BIP32Error::InvalidWord(word) => format!("The word '{}' is not valid in a words list", word),
+            BIP32Error::DuplicateWord(word) => format!("The word '{}' is present several times in the words list", word),
+            BIP32Error::InvalidSeedLength(len) => format!("The seed length ({} bytes) should be between 16 and 64 bytes", len),
+            BIP32Error::InvalidDerivationPath(path) => format!("The derivation path '{}' is not valid", path),
+            BIP32Error::InvalidChildNumber(index) => format!("The child index {} should be lower than 2^31", index),
+            BIP32Error::InvalidPrivateKey => String::from("The private key is not valid"),
+            BIP32Error::InvalidPublicKey => String::from("The public key is not valid"),
+            BIP32Error::HardenedDerivationFromPublicKey => String::from("Hardened child can't be derived from a public key"),
+            BIP32Error::NonHardenedDerivation(index) => format!("The child {} is not hardened, only hardened derivation is supported", index),
+            BIP32Error::MaxDepthExceeded => String::from("The maximum depth (255) is exceeded"),
+            BIP32Error::InvalidExtendedKey(error) => format!("The extended key is not valid : {}", error),
+            BIP32Error::InvalidExtendedKeyChecksum => String::from("The extended key checksum is not valid"),
+            BIP32Error::HmacError(error) => format!("Error when computing HMAC {}", error),
+            BIP32Error::InvalidShareChecksum => String::from("The share checksum is not valid"),
+            BIP32Error::InvalidShare(error) => format!("The share is not valid : {}", error),
+            BIP32Error::InvalidShareSet(error) => format!("The shares can't be combined : {}", error),
+            BIP32Error::InvalidShareDigest => String::from("The digest of the recovered secret is not valid, the shares are not consistent"),
+            BIP32Error::InvalidSharingParameters(error) => format!("The sharing parameters are not valid : {}", error),
+            BIP32Error::InvalidPassphrase => String::from("The passphrase must only contain printable ASCII characters"),
+            BIP32Error::InvalidBIP85Parameter(error) => format!("The BIP85 parameter is not valid : {}", error),
+            BIP32Error::InvalidMasterSecretLength(len) => format!("The master secret length ({} bytes) should be at least 16 bytes and even", len),
+            BIP32Error::InvalidEntropySource(error) => format!("The entropy source is not valid : {}", error),
+            BIP32Error::InsufficientEntropy(provided, required) => format!("The input carries {} bits of entropy, at least {} bits are required", provided, required),
+            BIP32Error::TooManyUnknownWords(nb) => format!("The phrase has {} unknown words, at most 2 can be recovered", nb),
+            BIP32Error::InvalidPassphraseMask(mask) => format!("The passphrase mask '{}' is not valid", mask),
+            BIP32Error::RecoveryCancelled => String::from("The recovery has been cancelled"),
+            BIP32Error::ChecksumAlreadyValid => String::from("The mnemonic checksum is already valid, there's nothing to recover"),
         }
     }
 }
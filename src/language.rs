@@ -1,5 +1,20 @@
-use std::{fs, path::Path};
-use crate::{error::BIP32Error, utils};
+use std::{collections::HashSet, sync::Arc};
+
+use zeroize::Zeroizing;
+
+use crate::{error::BIP32Error, utils, NB_WORDS};
+
+/* Config */
+const SUGGESTION_PREFIX_LEN: usize = 4;
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+const JAPANESE_SEPARATOR: &str = "\u{3000}";
+
+///
+/// Words lists embedded at build time from "src/words/*.txt" (see build.rs)
+///
+mod words_list {
+    include!(concat!(env!("OUT_DIR"), "/words.rs"));
+}
 
 ///
 /// Number of words in mnemonic
@@ -13,6 +28,21 @@ pub enum WordsCount {
     Words24,
 }
 
+impl WordsCount {
+    ///
+    /// Get the number of words
+    ///
+    pub fn nb_words(&self) -> usize {
+        match self {
+            WordsCount::Words12 => 12,
+            WordsCount::Words15 => 15,
+            WordsCount::Words18 => 18,
+            WordsCount::Words21 => 21,
+            WordsCount::Words24 => 24,
+        }
+    }
+}
+
 ///
 /// Create words count from number
 ///
@@ -31,6 +61,7 @@ impl From<usize> for WordsCount {
 ///
 /// The mnemonic lang
 ///
+#[derive(Debug, Clone, PartialEq)]
 pub enum Language {
     /// English words, bind to "english.txt"
     English,
@@ -48,72 +79,325 @@ pub enum Language {
     Spanish,
     /// Czech republic words, bind to "czech.txt"
     Czech,
+    /// Chinese simplified words, bind to "chinese_simplified.txt"
+    ChineseSimplified,
+    /// Chinese traditional words, bind to "chinese_traditional.txt"
+    ChineseTraditional,
+    /// Words list provided by the caller (see `Words::from_lines`)
+    Custom(Arc<Words>),
+}
+
+impl Language {
+    ///
+    /// Get all the supported languages
+    ///
+    pub fn all() -> Vec<Language> {
+        vec![
+            Language::English,
+            Language::French,
+            Language::Italian,
+            Language::Japanese,
+            Language::Korean,
+            Language::Portugese,
+            Language::Spanish,
+            Language::Czech,
+            Language::ChineseSimplified,
+            Language::ChineseTraditional,
+        ]
+    }
+
+    ///
+    /// Separator of the words when the phrase is displayed, the Japanese phrases are written with
+    /// ideographic spaces (U+3000) as in the bip32JP vectors. The NFKD normalization replaces it
+    /// with an ASCII space, so both phrases give the same seed
+    ///
+    pub fn separator(&self) -> &'static str {
+        match self {
+            Language::Japanese => JAPANESE_SEPARATOR,
+            _ => " ",
+        }
+    }
+
+    ///
+    /// Split a phrase into words. Chinese words are a single character, so the phrase
+    /// can be written with or without spaces between them.
+    /// Any whitespace separates the words (ASCII, ideographic or full-width spaces, tabulations...)
+    ///
+    pub fn split_phrase(&self, phrase: &str) -> Vec<String> {
+        match self {
+            Language::ChineseSimplified | Language::ChineseTraditional => phrase
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .map(String::from)
+                .collect(),
+            _ => phrase.split_whitespace().map(|word| word.to_owned()).collect(),
+        }
+    }
+
+    ///
+    /// Detect the language of a (complete or partial) mnemonic phrase, cheap enough to be called at each keystroke.
+    /// Only the languages containing every word are kept, the last word is still being typed and only has to
+    /// start a word of the list (unless the phrase ends with a space), the languages where it's already
+    /// a complete word are preferred. Some words are shared between lists
+    /// (English / French), in this case all the candidates are returned in the error.
+    ///
+    pub fn detect(phrase: String) -> Result<Language, BIP32Error> {
+        let phrase = Zeroizing::new(utils::to_utf8_nfkd(phrase));
+
+        if phrase.trim().is_empty() {
+            return Err(BIP32Error::InvalidWordsCount(0));
+        }
+        let is_last_word_complete = phrase.ends_with(char::is_whitespace);
+
+        // (Language, the last word is a complete word of the list)
+        let mut candidates: Vec<(Language, bool)> = vec![];
+        for language in Language::all() {
+            let phrase_words = Zeroizing::new(language.split_phrase(&phrase));
+            let words = Words::load(language.clone());
+            let (last_word, complete_words) = match is_last_word_complete {
+                true => (None, phrase_words.as_slice()),
+                false => (phrase_words.last(), &phrase_words[..phrase_words.len() - 1]),
+            };
+
+            // Binary searches, stopped at the first word missing from the list
+            if complete_words.iter().all(|word| words.index_of(word).is_some())
+                && last_word.is_none_or(|word| words.has_prefix(word))
+            {
+                candidates.push((
+                    language,
+                    last_word.is_none_or(|word| words.index_of(word).is_some()),
+                ));
+            }
+        }
+
+        // A complete last word wins over the start of a longer word ("about" / "aboutir")
+        if candidates.iter().any(|(_, is_complete)| *is_complete) {
+            candidates.retain(|(_, is_complete)| *is_complete);
+        }
+        let mut candidates: Vec<Language> =
+            candidates.into_iter().map(|(language, _)| language).collect();
+
+        match candidates.len() {
+            0 => Err(BIP32Error::UnknownLanguage),
+            1 => Ok(candidates.remove(0)),
+            _ => Err(BIP32Error::AmbiguousLanguage(candidates)),
+        }
+    }
+}
+
+///
+/// Storage of the 2048 words, embedded in the binary or provided by the caller
+///
+#[derive(Debug, Clone, PartialEq)]
+enum WordsList {
+    Embedded(&'static [&'static str; NB_WORDS]),
+    Custom(Arc<[String]>),
+}
+
+impl WordsList {
+    fn get(&self, index: usize) -> Option<&str> {
+        match self {
+            WordsList::Embedded(list) => list.get(index).copied(),
+            WordsList::Custom(list) => list.get(index).map(|word| word.as_str()),
+        }
+    }
+
+    fn iter(&self) -> impl DoubleEndedIterator<Item = &str> + '_ {
+        (0..NB_WORDS).filter_map(move |index| self.get(index))
+    }
+}
+
+///
+/// Order of the words sorted by bytes, lists not sorted by bytes keep the indexes of the words in this order
+///
+#[derive(Debug, Clone, PartialEq)]
+enum WordsOrder {
+    Sorted,
+    Embedded(&'static [u16; NB_WORDS]),
+    Custom(Arc<[u16]>),
+}
+
+impl WordsOrder {
+    ///
+    /// Index in the list of the word at this position in the sorted order
+    ///
+    fn index(&self, position: usize) -> usize {
+        match self {
+            WordsOrder::Sorted => position,
+            WordsOrder::Embedded(order) => order[position] as usize,
+            WordsOrder::Custom(order) => order[position] as usize,
+        }
+    }
 }
 
 ///
 /// The 2048 words associate to the current language
 ///
+#[derive(Debug, Clone, PartialEq)]
 pub struct Words {
-    list: Vec<String>,
-    language: Language,
+    list: WordsList,
+    order: WordsOrder,
+    // None for a custom list, see `current_language`
+    language: Option<Language>,
 }
 
 impl Words {
     ///
-    /// Load all words from lang file
+    /// Load all words from lang list, embedded in the binary
     ///
-    pub fn load(language: Language) -> Result<Words, BIP32Error> {
-        let language_content = Words::read_file(&language)?;
-        let words = Words::read_words(language_content)?;
+    pub fn load(language: Language) -> Words {
+        let (list, order) = match language {
+            Language::English => (&words_list::ENGLISH, &words_list::ENGLISH_ORDER),
+            Language::French => (&words_list::FRENCH, &words_list::FRENCH_ORDER),
+            Language::Italian => (&words_list::ITALIAN, &words_list::ITALIAN_ORDER),
+            Language::Japanese => (&words_list::JAPANESE, &words_list::JAPANESE_ORDER),
+            Language::Korean => (&words_list::KOREAN, &words_list::KOREAN_ORDER),
+            Language::Portugese => (&words_list::PORTUGESE, &words_list::PORTUGESE_ORDER),
+            Language::Spanish => (&words_list::SPANISH, &words_list::SPANISH_ORDER),
+            Language::Czech => (&words_list::CZECH, &words_list::CZECH_ORDER),
+            Language::ChineseSimplified => (
+                &words_list::CHINESE_SIMPLIFIED,
+                &words_list::CHINESE_SIMPLIFIED_ORDER,
+            ),
+            Language::ChineseTraditional => (
+                &words_list::CHINESE_TRADITIONAL,
+                &words_list::CHINESE_TRADITIONAL_ORDER,
+            ),
+            // Only the reference to the list is cloned
+            Language::Custom(words) => return words.as_ref().clone(),
+        };
 
-        Ok(Words {
-            list: words,
-            language,
-        })
+        Words {
+            list: WordsList::Embedded(list),
+            order: match order {
+                Some(order) => WordsOrder::Embedded(order),
+                None => WordsOrder::Sorted,
+            },
+            language: Some(language),
+        }
     }
 
     ///
-    /// Return the current language
-    /// 
-    pub fn current_language(&self) -> &Language {
-        &self.language
+    /// Return the current language, a custom list returns `Language::Custom` with a copy of the list
+    ///
+    pub fn current_language(&self) -> Language {
+        match &self.language {
+            Some(language) => language.clone(),
+            // Only the references to the list and the order are cloned
+            None => Language::Custom(Arc::new(self.clone())),
+        }
     }
 
     ///
-    /// Open and read the file associate to current language
+    /// Create a custom words list from a content with one word per line.
+    /// The list must have 2048 unique words, each word is normalized to NFKD
     ///
-    fn read_file(language: &Language) -> Result<String, BIP32Error> {
-        let read_file = |path| {
-            fs::read_to_string(Path::new(path)).map_err(|e| BIP32Error::ReadFile(e.to_string()))
+    pub fn from_lines(content: &str) -> Result<Words, BIP32Error> {
+        let words: Vec<String> = content
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .map(|line| utils::to_utf8_nfkd(line.to_owned()))
+            .collect();
+
+        if words.len() != NB_WORDS {
+            return Err(BIP32Error::InvalidWordsCount(words.len()));
+        }
+
+        // A word with a space inside could not be split back from a phrase
+        if let Some(word) = words.iter().find(|word| word.contains(char::is_whitespace)) {
+            return Err(BIP32Error::InvalidWord(word.to_owned()));
+        }
+
+        let mut unique_words = HashSet::new();
+        if let Some(word) = words.iter().find(|word| !unique_words.insert(word.as_str())) {
+            return Err(BIP32Error::DuplicateWord(word.to_owned()));
+        }
+
+        let order = match words.windows(2).all(|pair| pair[0] <= pair[1]) {
+            true => WordsOrder::Sorted,
+            false => {
+                let mut order = (0..NB_WORDS as u16).collect::<Vec<u16>>();
+                order.sort_by(|a, b| words[*a as usize].cmp(&words[*b as usize]));
+                WordsOrder::Custom(order.into())
+            }
         };
 
-        Ok(match language {
-            Language::English => read_file("src/words/english.txt")?,
-            Language::French => read_file("src/words/french.txt")?,
-            Language::Italian => read_file("src/words/italian.txt")?,
-            Language::Japanese => read_file("src/words/japanese.txt")?,
-            Language::Korean => read_file("src/words/korean.txt")?,
-            Language::Portugese => read_file("src/words/portugese.txt")?,
-            Language::Spanish => read_file("src/words/spanish.txt")?,
-            Language::Czech => read_file("src/words/czech.txt")?,
+        Ok(Words {
+            list: WordsList::Custom(words.into()),
+            order,
+            language: None,
         })
     }
 
     ///
-    /// Read words and split them to vector
+    /// Get the minimum number of first characters needed to identify each word.
+    /// BIP39 recommends lists where the first 4 letters are enough
     ///
-    fn read_words(content: String) -> Result<Vec<String>, BIP32Error> {
-        let words: Vec<String> = content
-            .split('\n')
-            .into_iter()
-            .map(|x| utils::to_utf8_nfkd(x.trim().to_owned()))
-            .collect();
+    pub fn unique_prefix_len(&self) -> usize {
+        let max_len = self.list.iter().map(|word| word.chars().count()).max().unwrap_or(0);
 
-        if words.len() != 2048 {
-            return Err(BIP32Error::InvalidWordsCount(words.len()));
+        (1..max_len)
+            .find(|len| {
+                let mut prefixes = HashSet::new();
+                self.list
+                    .iter()
+                    .all(|word| prefixes.insert(word.chars().take(*len).collect::<String>()))
+            })
+            .unwrap_or(max_len)
+    }
+
+    ///
+    /// Get the word at the index (0 - 2047) of the list
+    ///
+    /// Panics if the index is not lower than 2048
+    ///
+    pub fn word(&self, index: u16) -> &str {
+        self.list
+            .get(index as usize)
+            .unwrap_or_else(|| panic!("The word index {} should be lower than {}", index, NB_WORDS))
+    }
+
+    ///
+    /// Get the index (0 - 2047) of the word, with a binary search on the words sorted by bytes
+    ///
+    pub fn index_of(&self, word: &str) -> Option<u16> {
+        let position = self.lower_bound(word);
+
+        (position < NB_WORDS)
+            .then(|| self.order.index(position))
+            .filter(|index| self.list.get(*index) == Some(word))
+            .map(|index| index as u16)
+    }
+
+    ///
+    /// Check if a word of the list starts with the prefix
+    ///
+    fn has_prefix(&self, prefix: &str) -> bool {
+        let position = self.lower_bound(prefix);
+
+        position < NB_WORDS
+            && self
+                .list
+                .get(self.order.index(position))
+                .is_some_and(|word| word.starts_with(prefix))
+    }
+
+    ///
+    /// Position in the sorted order of the first word not lower than the given word
+    ///
+    fn lower_bound(&self, word: &str) -> usize {
+        let (mut low, mut high) = (0, NB_WORDS);
+
+        while low < high {
+            let middle = (low + high) / 2;
+            match self.list.get(self.order.index(middle)) {
+                Some(w) if w < word => low = middle + 1,
+                _ => high = middle,
+            }
         }
 
-        Ok(words)
+        low
     }
 
     ///
@@ -125,11 +409,8 @@ impl Words {
         for i in words_index {
             words.push(
                 self.list
-                    .clone()
-                    .into_iter()
-                    .enumerate()
-                    .find(|(index_word, _)| *i == *index_word as u16)
-                    .map(|f| f.1)
+                    .get(*i as usize)
+                    .map(|word| word.to_string())
                     .ok_or(BIP32Error::WordNotFound(*i))?,
             );
         }
@@ -142,69 +423,235 @@ impl Words {
         Ok(words)
     }
 
+    ///
+    /// Get the index (0 - 2047) associated to each given words
+    ///
+    pub fn get_index_from_words(&self, words: &[String]) -> Result<Vec<u16>, BIP32Error> {
+        words
+            .iter()
+            .map(|word| {
+                self.index_of(word)
+                    .ok_or_else(|| BIP32Error::UnknownWord(word.to_owned()))
+            })
+            .collect()
+    }
+
     ///
     /// Does the current language have this word in the dictionnary ?
     ///
     pub fn contain_word(&self, word: String) -> bool {
-        self.list.iter().any(|w| w == &word)
+        self.index_of(&word).is_some()
+    }
+
+    ///
+    /// Suggest the words of the list close to the input, to correct a mistyped word.
+    /// The words are compared without case and Latin diacritics ("etude" matches "étude"), a word is suggested
+    /// when its Damerau-Levenshtein distance to the input is at most 2 or when it shares the input 4 first letters.
+    /// The suggestions are ranked by distance, then the words sharing the prefix first
+    ///
+    pub fn suggest(&self, input: &str) -> Vec<&str> {
+        let input = Zeroizing::new(utils::fold_word(input));
+        let input_prefix =
+            Zeroizing::new(input.chars().take(SUGGESTION_PREFIX_LEN).collect::<String>());
+
+        let mut suggestions = self
+            .list
+            .iter()
+            .filter_map(|word| {
+                let folded_word = utils::fold_word(word);
+                let distance = utils::damerau_levenshtein(&input, &folded_word);
+                let shared_prefix = input_prefix.chars().count() == SUGGESTION_PREFIX_LEN
+                    && folded_word.starts_with(input_prefix.as_str());
+
+                (distance <= MAX_SUGGESTION_DISTANCE || shared_prefix)
+                    .then_some((distance, !shared_prefix, word))
+            })
+            .collect::<Vec<(usize, bool, &str)>>();
+        suggestions.sort();
+
+        suggestions.into_iter().map(|(_, _, word)| word).collect()
+    }
+
+    ///
+    /// Get all the words starting with the prefix, in the words list order.
+    /// The words are found with a binary search on the words sorted by bytes
+    ///
+    pub fn complete(&self, prefix: &str) -> Vec<&str> {
+        let prefix = Zeroizing::new(utils::to_utf8_nfkd(prefix.to_owned()));
+
+        let mut indexes = (self.lower_bound(&prefix)..NB_WORDS)
+            .map(|position| self.order.index(position))
+            .take_while(|index| self.list.get(*index).is_some_and(|word| word.starts_with(prefix.as_str())))
+            .collect::<Vec<usize>>();
+        indexes.sort_unstable();
+
+        indexes.into_iter().filter_map(|index| self.list.get(index)).collect()
+    }
+
+    ///
+    /// Expand an abbreviated word (as the 4 first letters stored on metal backups) to the full word.
+    /// A word of the list is kept as is, even if it's the prefix of other words ("act" for "action")
+    ///
+    pub fn expand(&self, abbreviation: &str) -> Result<&str, BIP32Error> {
+        let mut completions = self.complete(abbreviation);
+        let abbreviation_nfkd = utils::to_utf8_nfkd(abbreviation.to_owned());
+        if let Some(word) = completions.iter().find(|word| **word == abbreviation_nfkd) {
+            return Ok(word);
+        }
+
+        match completions.len() {
+            0 => Err(BIP32Error::UnknownWord(abbreviation.to_owned())),
+            1 => Ok(completions.remove(0)),
+            _ => Err(BIP32Error::AmbiguousWord(
+                abbreviation.to_owned(),
+                completions.iter().map(|word| word.to_string()).collect(),
+            )),
+        }
     }
 
     ///
     /// Generate mnemonic phrase from current words list
     ///
     pub fn get_phrase(&self) -> String {
-        Words::get_phrase_from_words(&self.list)
+        self.list.iter().collect::<Vec<&str>>().join(" ")
     }
 
     ///
     /// Aggregate the list of string to build a string
     ///
-    pub fn get_phrase_from_words(words: &Vec<String>) -> String {
+    pub fn get_phrase_from_words(words: &[String]) -> String {
         words.join(" ")
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::error::BIP32Error;
+    use std::sync::Arc;
 
-    use super::{Language, Words};
+    use crate::{error::BIP32Error, utils};
 
-    fn all_language() -> Vec<Language> {
-        vec![Language::English, Language::French]
+    use super::{Language, Words, WordsOrder};
+
+    ///
+    /// Load the 2048 words associated to each lang
+    ///
+    #[test]
+    fn test_load_words_should_succeed() {
+        for lang in Language::all().into_iter() {
+            let words = Words::load(lang.clone());
+            assert_eq!(words.list.iter().count(), 2048);
+            assert!(words.list.iter().all(|word| utils::to_utf8_nfkd(word.to_string()) == word));
+            assert_eq!(words.current_language(), lang);
+        }
     }
 
     ///
-    /// Check if the file associated to the current lang is not empty
+    /// Binary search on sorted lists should find every word
     ///
     #[test]
-    fn test_read_file_should_succeed() {
-        for lang in all_language().iter() {
-            let file_content = Words::read_file(&lang).unwrap();
-            assert!(!file_content.is_empty());
+    fn test_contain_word() {
+        for lang in Language::all().into_iter() {
+            let words = Words::load(lang);
+            assert!(words.list.iter().all(|word| words.contain_word(word.to_string())));
+            assert!(!words.contain_word("bitcoin".to_owned()));
         }
     }
 
     ///
-    /// Read the 2048 words associated to current lang
+    /// Suggestions ignore the accents and are ranked by distance
     ///
     #[test]
-    fn test_split_words_should_succeed() {
-        for lang in all_language().iter() {
-            let file_content = Words::read_file(lang).unwrap();
-            let words = Words::read_words(file_content).unwrap();
-            assert_eq!(words.len(), 2048);
+    fn test_suggest() {
+        let english = Words::load(Language::English);
+        assert_eq!(english.suggest("abandon"), vec!["abandon"]);
+        assert_eq!(english.suggest("abandn"), vec!["abandon"]);
+        assert_eq!(english.suggest("abnadon"), vec!["abandon"]);
+        assert_eq!(english.suggest("zo")[0], "zoo");
+        assert!(english.suggest("bitcoin").is_empty());
+
+        // The words sharing the 4 first letters are suggested even when far from the input
+        let suggestions = english.suggest("abstruse");
+        assert_eq!(suggestions, vec!["abstract"]);
+
+        let french = Words::load(Language::French);
+        assert_eq!(french.suggest("etude")[0], utils::to_utf8_nfkd("étude".to_owned()));
+        assert_eq!(french.suggest("ÉTUDE")[0], utils::to_utf8_nfkd("étude".to_owned()));
+
+        let spanish = Words::load(Language::Spanish);
+        assert_eq!(spanish.suggest("abaco")[0], utils::to_utf8_nfkd("ábaco".to_owned()));
+
+        // か and が are different kana, "あんかい" is one edit away from "あんがい" as from "あんい"
+        let japanese = Words::load(Language::Japanese);
+        assert_eq!(
+            japanese.suggest("あんかい")[..2],
+            [utils::to_utf8_nfkd("あんい".to_owned()), utils::to_utf8_nfkd("あんがい".to_owned())]
+        );
+        assert_eq!(japanese.suggest("あんがい")[0], utils::to_utf8_nfkd("あんがい".to_owned()));
+    }
+
+    ///
+    /// Every word is found at its index, in sorted and unsorted lists
+    ///
+    #[test]
+    fn test_word_and_index_of() {
+        for lang in Language::all().into_iter() {
+            let words = Words::load(lang);
+            for (index, word) in words.list.iter().enumerate() {
+                assert_eq!(words.word(index as u16), word);
+                assert_eq!(words.index_of(word), Some(index as u16));
+            }
+            assert_eq!(words.index_of("bitcoin"), None);
+            assert_eq!(words.index_of(""), None);
+            assert_eq!(words.index_of("\u{10FFFF}"), None);
         }
     }
 
     ///
-    /// Split words from a bad parameter (empty string)
+    /// Out of range index should panic
+    ///
+    #[test]
+    #[should_panic]
+    fn test_word_out_of_range_should_panic() {
+        Words::load(Language::English).word(2048);
+    }
+
+    ///
+    /// Completions are found in sorted and unsorted lists
     ///
     #[test]
-    fn test_split_words_from_empty_string_should_err() {
-        // Expected "InvalidWordsCount(1)" because split an empty string result in a 1 lenght vec
-        let expected_error = Err(BIP32Error::InvalidWordsCount(1));
-        assert_eq!(Words::read_words(String::from("")), expected_error);
+    fn test_complete() {
+        let english = Words::load(Language::English);
+        assert_eq!(english.complete("aban"), vec!["abandon"]);
+        assert_eq!(english.complete("act"), vec!["act", "action", "actor", "actress", "actual"]);
+        assert_eq!(english.complete("zoo"), vec!["zoo"]);
+        assert_eq!(english.complete("").len(), 2048);
+        assert!(english.complete("bitc").is_empty());
+
+        let french = Words::load(Language::French);
+        assert_eq!(french.complete("étu"), vec![utils::to_utf8_nfkd("étude".to_owned())]);
+
+        // The chinese lists are not sorted
+        let chinese = Words::load(Language::ChineseSimplified);
+        assert_eq!(chinese.complete("的"), vec!["的"]);
+    }
+
+    ///
+    /// Abbreviations are expanded to a single word, exact words are kept
+    ///
+    #[test]
+    fn test_expand() {
+        let english = Words::load(Language::English);
+        assert_eq!(english.expand("abou"), Ok("about"));
+        assert_eq!(english.expand("ABOU"), Err(BIP32Error::UnknownWord("ABOU".to_owned())));
+        assert_eq!(english.expand("act"), Ok("act"));
+        assert_eq!(english.expand("bitc"), Err(BIP32Error::UnknownWord("bitc".to_owned())));
+        assert_eq!(
+            english.expand("abo"),
+            Err(BIP32Error::AmbiguousWord(
+                "abo".to_owned(),
+                vec!["about".to_owned(), "above".to_owned()]
+            ))
+        );
     }
 
     ///
@@ -213,7 +660,7 @@ mod tests {
     #[test]
     fn test_get_words_from_index() {
         let words_index = vec![0, 2, 4, 6, 8, 10];
-        let words = Words::load(Language::English).unwrap();
+        let words = Words::load(Language::English);
 
         let selected_words = words.get_words_from_index(&words_index).unwrap();
 
@@ -232,20 +679,189 @@ mod tests {
             0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
             0, 0, 102,
         ];
-        let words = Words::load(Language::English).unwrap();
+        let words = Words::load(Language::English);
 
         let selected_words = words.get_words_from_index(&words_index).unwrap();
 
         assert_eq!(selected_words.len(), words_index.len());
     }
 
+    ///
+    /// Get the words index from the selected words
+    ///
+    #[test]
+    fn test_get_index_from_words() {
+        let words = Words::load(Language::English);
+        let selected_words = vec!["abandon".to_owned(), "able".to_owned(), "zoo".to_owned()];
+
+        assert_eq!(words.get_index_from_words(&selected_words).unwrap(), vec![0, 2, 2047]);
+        assert_eq!(
+            words.get_index_from_words(&["bitcoin".to_owned()]),
+            Err(BIP32Error::UnknownWord("bitcoin".to_owned()))
+        );
+    }
+
+    ///
+    /// Detect the language of a phrase
+    ///
+    #[test]
+    fn test_detect_language() {
+        assert_eq!(
+            Language::detect("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".to_owned()),
+            Ok(Language::English)
+        );
+        assert_eq!(
+            Language::detect("abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abeille".to_owned()),
+            Ok(Language::French)
+        );
+        assert_eq!(
+            Language::detect("ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco abierto".to_owned()),
+            Ok(Language::Spanish)
+        );
+        // Partial phrase, while the user is typing
+        assert_eq!(Language::detect("가격 가격".to_owned()), Ok(Language::Korean));
+        assert_eq!(Language::detect("abandon abandon wi".to_owned()), Ok(Language::English));
+        assert_eq!(Language::detect("abaisser abeil".to_owned()), Ok(Language::French));
+        // Chinese phrase without spaces
+        assert_eq!(Language::detect("层罐阴伤".to_owned()), Ok(Language::ChineseSimplified));
+        assert_eq!(Language::detect("層罐陰傷".to_owned()), Ok(Language::ChineseTraditional));
+    }
+
+    ///
+    /// Words shared between several lists should give all the candidates
+    ///
+    #[test]
+    fn test_detect_language_ambiguous_or_unknown() {
+        assert_eq!(
+            Language::detect("abandon".to_owned()),
+            Err(BIP32Error::AmbiguousLanguage(vec![Language::English, Language::French]))
+        );
+        assert_eq!(
+            Language::detect("abandon bitcoin".to_owned()),
+            Err(BIP32Error::UnknownLanguage)
+        );
+        // The last word is only a prefix while it's not followed by a space
+        assert_eq!(
+            Language::detect("abandon abandon abou".to_owned()),
+            Err(BIP32Error::AmbiguousLanguage(vec![Language::English, Language::French]))
+        );
+        assert_eq!(
+            Language::detect("abandon abandon abou ".to_owned()),
+            Err(BIP32Error::UnknownLanguage)
+        );
+        assert_eq!(
+            Language::detect("abandon bitc".to_owned()),
+            Err(BIP32Error::UnknownLanguage)
+        );
+        assert_eq!(
+            Language::detect("".to_owned()),
+            Err(BIP32Error::InvalidWordsCount(0))
+        );
+    }
+
+    ///
+    /// Chinese phrases can be written with or without spaces
+    ///
+    #[test]
+    fn test_split_phrase() {
+        let expected = vec!["的".to_owned(), "在".to_owned(), "了".to_owned()];
+        assert_eq!(Language::ChineseSimplified.split_phrase("的 在 了"), expected);
+        assert_eq!(Language::ChineseSimplified.split_phrase("的在了"), expected);
+        assert_eq!(Language::ChineseTraditional.split_phrase(" 的在 了 "), expected);
+        assert_eq!(
+            Language::English.split_phrase("abandon  about"),
+            vec!["abandon".to_owned(), "about".to_owned()]
+        );
+
+        // Ideographic and mixed whitespaces
+        let expected = vec!["あいこくしん".to_owned(), "あおぞら".to_owned(), "われる".to_owned()];
+        assert_eq!(Language::Japanese.split_phrase("あいこくしん\u{3000}あおぞら\u{3000}われる"), expected);
+        assert_eq!(Language::Japanese.split_phrase("\u{3000}あいこくしん \u{3000}あおぞら\tわれる\n"), expected);
+    }
+
+    ///
+    /// The Japanese words are separated by ideographic spaces when displayed
+    ///
+    #[test]
+    fn test_separator() {
+        assert_eq!(Language::Japanese.separator(), "\u{3000}");
+        assert_eq!(Language::English.separator(), " ");
+        assert_eq!(Language::ChineseSimplified.separator(), " ");
+        assert_eq!(utils::to_utf8_nfkd(Language::Japanese.separator().to_owned()), " ");
+    }
+
+    ///
+    /// Create a custom words list from lines
+    ///
+    #[test]
+    fn test_custom_words_from_lines() {
+        let english = Words::load(Language::English);
+        let content = english.list.iter().rev().collect::<Vec<&str>>().join("\r\n");
+
+        let words = Words::from_lines(&content).unwrap();
+        assert_eq!(words.get_words_from_index(&vec![0, 2047]).unwrap(), vec!["zoo", "abandon"]);
+        assert!(matches!(words.order, WordsOrder::Custom(_)));
+        assert!(words.contain_word("abandon".to_owned()));
+        assert_eq!(words.index_of("abandon"), Some(2047));
+        assert_eq!(words.unique_prefix_len(), 4);
+
+        // Sorted custom list should be detected
+        let sorted_words = Words::from_lines(&english.get_phrase().replace(' ', "\n")).unwrap();
+        assert_eq!(sorted_words.order, WordsOrder::Sorted);
+        let custom = Language::Custom(Arc::new(sorted_words));
+        assert_eq!(Words::load(custom.clone()).get_phrase(), english.get_phrase());
+        assert_eq!(Words::load(custom.clone()).current_language(), custom);
+    }
+
+    ///
+    /// Custom words list with a bad count, duplicate or invalid words should err
+    ///
+    #[test]
+    fn test_custom_words_from_lines_should_err() {
+        let english = Words::load(Language::English);
+        let mut lines: Vec<&str> = english.list.iter().collect();
+
+        assert_eq!(
+            Words::from_lines(&lines[1..].join("\n")),
+            Err(BIP32Error::InvalidWordsCount(2047))
+        );
+
+        lines[1] = "abandon";
+        assert_eq!(
+            Words::from_lines(&lines.join("\n")),
+            Err(BIP32Error::DuplicateWord("abandon".to_owned()))
+        );
+
+        lines[1] = "ability";
+        lines[2] = "able\tcable";
+        assert_eq!(
+            Words::from_lines(&lines.join("\n")),
+            Err(BIP32Error::InvalidWord("able\tcable".to_owned()))
+        );
+    }
+
+    ///
+    /// Number of first letters needed to identify a word
+    ///
+    #[test]
+    fn test_unique_prefix_len() {
+        assert_eq!(Words::load(Language::English).unique_prefix_len(), 4);
+        assert_eq!(Words::load(Language::Italian).unique_prefix_len(), 4);
+
+        // "abandon" and "abandoned" are only different from the 8th letter
+        let english = Words::load(Language::English);
+        let mut lines: Vec<&str> = english.list.iter().collect();
+        lines[1] = "abandoned";
+        assert_eq!(Words::from_lines(&lines.join("\n")).unwrap().unique_prefix_len(), 8);
+    }
+
     ///
     /// Basic words concatenation
     ///
     #[test]
     fn test_build_sentence() {
         assert_eq!(
-            Words::get_phrase_from_words(&vec![
+            Words::get_phrase_from_words(&[
                 "Hi".to_owned(),
                 "im".to_owned(),
                 "gozu".to_owned()
@@ -8,10 +8,18 @@
 ///     <https://github.com/bip32JP/bip32JP.github.io/blob/master/test_JP_BIP39.json>
 ///     <https://www.blockplate.com/pages/bip-39-wordlist>
 ///     <https://www.blockplate.com/blogs/blockplate/list-of-bip39-wallets-mnemonic-seed>
+pub mod address;
+pub mod bip32;
+pub mod bip85;
+pub mod card;
 pub mod entropy;
 pub mod error;
 pub mod language;
 pub mod mnemonic;
+pub mod passphrase_recovery;
+pub mod recovery;
+pub mod slip10;
+pub mod slip39;
 pub mod utils;
 
 pub use mnemonic::Mnemonic;
@@ -19,18 +27,24 @@ pub use mnemonic::Seed;
 pub use language::WordsCount;
 pub use language::Language;
 pub use entropy::Entropy;
+pub use card::{Card, Suit};
 pub use error::BIP32Error;
+pub use rand_core::CryptoRngCore;
+pub use bip32::{DerivationPath, ExtendedPrivateKey, ExtendedPublicKey};
+pub use slip10::{Curve, Slip10Key};
 
 /* Config */
 const NB_BITS_IN_BYTE: usize = 8;
 const ENTROPY_MULTIPLE: usize = 32;
 const BITS_LEN_ITERATION: usize = 11;
+const NB_WORDS: usize = 2048;
 
 /// Generate a new Mnemonic from given words count, with optional Passphrase and mnemonic words language.
+/// The entropy is generated with the given cryptographically secure RNG, or with the OS RNG when none is given.
 /// # Example
 /// ```
 /// use bip39::{WordsCount, Language};
-/// let mnemonic = bip39::generate_mnemonic(WordsCount::Words24, Language::English);
+/// let mnemonic = bip39::generate_mnemonic(WordsCount::Words24, Language::English, None);
 /// match mnemonic {
 ///     Ok(ok_mnemonic) => {
 ///         println!("{}", ok_mnemonic.to_string());
@@ -41,8 +55,15 @@ const BITS_LEN_ITERATION: usize = 11;
 ///     }
 /// }
 /// ```
-pub fn generate_mnemonic(nb_words: WordsCount, lang: Language) -> Result<Mnemonic, BIP32Error> {
-    Ok(Mnemonic::create(nb_words.into(), lang)?)
+pub fn generate_mnemonic(
+    nb_words: WordsCount,
+    lang: Language,
+    rng: Option<&mut dyn CryptoRngCore>,
+) -> Result<Mnemonic, BIP32Error> {
+    match rng {
+        Some(rng) => Mnemonic::create_with_rng(nb_words.into(), lang, rng),
+        None => Mnemonic::create(nb_words.into(), lang),
+    }
 }
 
 /// Generate a new Mnemonic from a given entropy
@@ -59,7 +80,7 @@ pub fn generate_mnemonic_from_entropy(
     entropy: Entropy,
     lang: Language,
 ) -> Result<Mnemonic, BIP32Error> {
-    Ok(Mnemonic::from_entropy(entropy, lang)?)
+    Mnemonic::from_entropy(entropy, lang)
 }
 
 /// Get seed from current Mnemonic phrase and passphrase
@@ -73,5 +94,9 @@ pub fn generate_mnemonic_from_entropy(
 /// assert_eq!(seed.to_hex(), "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04");
 /// ```
 pub fn get_seed_from_phrase(mnemonic_phrase: String, passphrase: Option<String>) -> Seed {
+    // The phrase and the passphrase are wiped once the seed is computed
+    let mnemonic_phrase = zeroize::Zeroizing::new(mnemonic_phrase);
+    let passphrase = zeroize::Zeroizing::new(passphrase);
+
     Seed::new(&mnemonic_phrase, &passphrase)
 }
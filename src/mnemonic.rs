@@ -1,64 +1,214 @@
-use std::{fmt::Display, u8};
+use std::fmt::{Debug, Display};
 
 use crate::{
     entropy::{Bytes, Entropy, EntropySize},
     error::BIP32Error,
     language::{Language, Words},
-    utils, BITS_LEN_ITERATION,
+    utils, BITS_LEN_ITERATION, ENTROPY_MULTIPLE, NB_BITS_IN_BYTE,
 };
 use bitreader::BitReader;
+use rand::{CryptoRng, RngCore};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
 
 ///
-/// Represent the BIP39 Mnemonic phrase
+/// Represent the BIP39 Mnemonic phrase, the words and the entropy are wiped from memory when dropped
 /// 
+#[derive(Zeroize, ZeroizeOnDrop)]
 pub struct Mnemonic {
     mnemonic_words: Vec<String>,
     entropy: Entropy,
+    #[zeroize(skip)]
+    lang: Language,
 }
 
+///
+/// Display the phrase with the separator of the language (ideographic space for Japanese)
+///
 impl Display for Mnemonic {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", &self.get_phrase())
+        let phrase = Zeroizing::new(self.mnemonic_words.join(self.lang.separator()));
+        write!(f, "{}", phrase.as_str())
+    }
+}
+
+///
+/// Never display the words, to avoid leaking the mnemonic into the logs
+///
+impl Debug for Mnemonic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Mnemonic")
+            .field("mnemonic_words", &format_args!("[REDACTED; {}]", self.mnemonic_words.len()))
+            .field("entropy", &self.entropy)
+            .finish()
     }
 }
 
 impl Mnemonic {
     /// 
-    /// Create a mnemonic from the given entropy size
+    /// Create a mnemonic from the given entropy size, with the OS cryptographically secure RNG
     /// 
     pub fn create(entropy_size: EntropySize, lang: Language) -> Result<Mnemonic, BIP32Error> {
         Mnemonic::from_entropy(Entropy::generate(entropy_size), lang)
     }
 
+    ///
+    /// Create a mnemonic from the given entropy size, with the given cryptographically secure RNG
+    ///
+    pub fn create_with_rng<R: RngCore + CryptoRng + ?Sized>(
+        entropy_size: EntropySize,
+        lang: Language,
+        rng: &mut R,
+    ) -> Result<Mnemonic, BIP32Error> {
+        Mnemonic::from_entropy(Entropy::generate_with_rng(entropy_size, rng), lang)
+    }
+
     ///
     /// Create a mnemonic from an existing entropy
     /// 
     pub fn from_entropy(mut entropy: Entropy, lang: Language) -> Result<Mnemonic, BIP32Error> {
         // Load all words from current language
-        let words = Words::load(lang)?;
+        let words = Words::load(lang.clone());
 
         // Generate list of mnemonic vector string
-        let mnemonic_words =
-            words.get_words_from_index(&Mnemonic::generate_word_index_list(&mut entropy)?)?;
+        let words_index = Zeroizing::new(Mnemonic::generate_word_index_list(&mut entropy)?);
+        let mnemonic_words = words.get_words_from_index(&words_index)?;
+
+        Ok(Mnemonic {
+            mnemonic_words,
+            entropy,
+            lang,
+        })
+    }
+
+    ///
+    /// Create a mnemonic from an existing phrase, checking that every word belongs to the language
+    /// words list and that the checksum is valid
+    ///
+    pub fn from_phrase(phrase: String, lang: Language) -> Result<Mnemonic, BIP32Error> {
+        let phrase = Zeroizing::new(utils::to_utf8_nfkd(phrase));
+        let mut mnemonic_words = Zeroizing::new(lang.split_phrase(&phrase));
+
+        // Load all words from current language
+        let words = Words::load(lang.clone());
+
+        if !Mnemonic::is_mnemonic_words_count_valid(mnemonic_words.len()) {
+            return Err(BIP32Error::InvalidWordsCount(mnemonic_words.len()));
+        }
+
+        let words_index = Zeroizing::new(words.get_index_from_words(&mnemonic_words)?);
+        let entropy = Mnemonic::entropy_from_word_index_list(&words_index)?;
 
         Ok(Mnemonic {
-            mnemonic_words: mnemonic_words,
-            entropy: entropy,
+            mnemonic_words: std::mem::take(&mut mnemonic_words),
+            entropy,
+            lang,
         })
     }
 
     ///
-    /// Get the mnemonic phrase
+    /// Create a mnemonic from a phrase where the words can be abbreviated ("aban aban ... abou"),
+    /// as the 4 first letters stored on metal backups. Each abbreviation must match a single word
+    ///
+    pub fn from_abbreviated_phrase(phrase: String, lang: Language) -> Result<Mnemonic, BIP32Error> {
+        let phrase = Zeroizing::new(utils::to_utf8_nfkd(phrase));
+        let words = Words::load(lang.clone());
+
+        let full_words = Zeroizing::new(
+            lang.split_phrase(&phrase)
+                .iter()
+                .map(|abbreviation| words.expand(abbreviation).map(str::to_owned))
+                .collect::<Result<Vec<String>, BIP32Error>>()?,
+        );
+
+        Mnemonic::from_phrase(Words::get_phrase_from_words(&full_words), lang)
+    }
+
+    ///
+    /// Create a mnemonic from an existing phrase without knowing its language.
+    /// When the words belong to several languages, the candidate with a valid checksum is selected
+    ///
+    pub fn from_phrase_any_language(phrase: String) -> Result<Mnemonic, BIP32Error> {
+        let phrase = Zeroizing::new(phrase);
+
+        match Language::detect(phrase.to_string()) {
+            Ok(lang) => Mnemonic::from_phrase(phrase.to_string(), lang),
+            Err(BIP32Error::AmbiguousLanguage(candidates)) => {
+                let mut valid_mnemonics: Vec<Mnemonic> = candidates
+                    .iter()
+                    .filter_map(|lang| Mnemonic::from_phrase(phrase.to_string(), lang.clone()).ok())
+                    .collect();
+
+                match valid_mnemonics.len() {
+                    0 => Err(BIP32Error::InvalidChecksum),
+                    1 => Ok(valid_mnemonics.remove(0)),
+                    _ => Err(BIP32Error::AmbiguousLanguage(candidates)),
+                }
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    ///
+    /// Write the same entropy with the words of another language.
+    /// The BIP39 seed is computed from the words, not from the entropy : the translated mnemonic restores
+    /// a different wallet (see `has_same_seed`), only the Substrate seed is kept
+    ///
+    pub fn to_language(&self, lang: Language) -> Result<Mnemonic, BIP32Error> {
+        Mnemonic::from_entropy(self.entropy.clone(), lang)
+    }
+
+    ///
+    /// Do both mnemonics give the same BIP39 seed (with the same passphrase) ?
+    /// The seed is derived from the normalized phrase, so a translated mnemonic gives a different seed
+    /// even if it has the same entropy
+    ///
+    pub fn has_same_seed(&self, other: &Mnemonic) -> bool {
+        self.mnemonic_words == other.mnemonic_words
+    }
+
+    ///
+    /// Get the normalized mnemonic phrase (NFKD, words separated by an ASCII space), as used for the seed.
+    /// Display the mnemonic to get the phrase with the separator of its language
     ///
     pub fn get_phrase(&self) -> String {
         Mnemonic::get_phrase_from_words(&self.mnemonic_words)
     }
 
+    ///
+    /// Get the normalized mnemonic phrase as `get_phrase`, wiped from memory when dropped
+    ///
+    pub fn phrase_zeroizing(&self) -> Zeroizing<String> {
+        Zeroizing::new(self.get_phrase())
+    }
+
+    ///
+    /// Get the Substrate (Polkadot) seed, compatible with `subkey` and polkadot.js
+    /// From documentation (<https://github.com/paritytech/substrate-bip39>)
+    ///     Unlike BIP39, the PBKDF2 password is the entropy instead of the mnemonic phrase,
+    ///     the salt is still "mnemonic" + password. The password is not normalized.
+    ///
+    pub fn to_substrate_seed(&self, password: &str) -> Seed {
+        let salt = ["mnemonic".as_bytes(), password.as_bytes()].concat();
+        let seed = utils::pbkdf2_hash(self.entropy.entropy.into_vec(), salt);
+
+        Seed {
+            val: Bytes::new(seed),
+        }
+    }
+
+    ///
+    /// Get the Substrate 32 bytes mini secret key (first half of the Substrate seed),
+    /// used to derive the sr25519 / ed25519 keys
+    ///
+    pub fn to_substrate_mini_secret(&self, password: &str) -> Bytes {
+        self.to_substrate_seed(password).get_bytes().take(32)
+    }
+
     ///
     /// Concatenate given words
-    /// 
-    fn get_phrase_from_words(words: &Vec<String>) -> String {
+    ///
+    fn get_phrase_from_words(words: &[String]) -> String {
         words.join(" ")
     }
 
@@ -82,6 +232,113 @@ impl Mnemonic {
         Ok(words_index)
     }
 
+    ///
+    /// Rebuild the entropy from the list of words index and verify the checksum
+    ///
+    pub(crate) fn entropy_from_word_index_list(words_index: &[u16]) -> Result<Entropy, BIP32Error> {
+        let entropy_checksum = Mnemonic::bits_from_word_index_list(words_index);
+        let entropy_nb_bytes = Mnemonic::entropy_nb_bits(words_index.len()) / NB_BITS_IN_BYTE;
+        let entropy = Entropy::from_bytes_vec(entropy_checksum[..entropy_nb_bytes].to_vec())?;
+
+        // The entropy is a multiple of 32 bits, the checksum (at most 8 bits) starts on the next byte
+        let checksum_shift = NB_BITS_IN_BYTE - entropy.checksum_nb_bits();
+        if entropy_checksum[entropy_nb_bytes] >> checksum_shift
+            != entropy.checksum().as_vec()[0] >> checksum_shift
+        {
+            return Err(BIP32Error::InvalidChecksum);
+        }
+
+        Ok(entropy)
+    }
+
+    ///
+    /// Write each index as a group of 11 bits, most significant bit first
+    ///
+    fn bits_from_word_index_list(words_index: &[u16]) -> Zeroizing<Vec<u8>> {
+        let nb_bits = words_index.len() * BITS_LEN_ITERATION;
+        let mut bits = Zeroizing::new(Vec::with_capacity(nb_bits.div_ceil(NB_BITS_IN_BYTE)));
+        let mut accumulator = 0u32;
+        let mut nb_pending_bits = 0;
+
+        for index in words_index {
+            accumulator = (accumulator << BITS_LEN_ITERATION) | *index as u32;
+            nb_pending_bits += BITS_LEN_ITERATION;
+            while nb_pending_bits >= NB_BITS_IN_BYTE {
+                nb_pending_bits -= NB_BITS_IN_BYTE;
+                bits.push((accumulator >> nb_pending_bits) as u8);
+            }
+        }
+        if nb_pending_bits > 0 {
+            bits.push((accumulator << (NB_BITS_IN_BYTE - nb_pending_bits)) as u8);
+        }
+        accumulator.zeroize();
+
+        bits
+    }
+
+    ///
+    /// The checksum length is ENT / 32, with ENT + CS = nb_words * 11
+    ///
+    fn entropy_nb_bits(nb_words: usize) -> usize {
+        nb_words * BITS_LEN_ITERATION * ENTROPY_MULTIPLE / (ENTROPY_MULTIPLE + 1)
+    }
+
+    ///
+    /// Get every valid mnemonic completing the 11, 14, 17, 20 or 23 chosen words with a last word.
+    /// The last word is made of free entropy bits followed by the checksum bits, so there are
+    /// 128, 64, 32, 16 or 8 candidates, ordered by their free entropy bits.
+    /// The supplemental random bits (coin flips...) are mixed (XOR) with the free entropy bits,
+    /// the first candidate is then the one whose free bits are the supplemental bits
+    ///
+    pub fn final_word_candidates(
+        partial_words: &[String],
+        lang: Language,
+        supplemental_bits: Option<u16>,
+    ) -> Result<Vec<Mnemonic>, BIP32Error> {
+        let nb_words = partial_words.len() + 1;
+        if !Mnemonic::is_mnemonic_words_count_valid(nb_words) {
+            return Err(BIP32Error::InvalidWordsCount(partial_words.len()));
+        }
+
+        let partial_words = Zeroizing::new(
+            partial_words
+                .iter()
+                .map(|word| utils::to_utf8_nfkd(word.to_owned()))
+                .collect::<Vec<String>>(),
+        );
+        let words = Words::load(lang.clone());
+        let mut words_index = Zeroizing::new(words.get_index_from_words(&partial_words)?);
+        words_index.push(0);
+
+        let entropy_nb_bytes = Mnemonic::entropy_nb_bits(nb_words) / NB_BITS_IN_BYTE;
+        let bits = Mnemonic::bits_from_word_index_list(&words_index);
+        let checksum_nb_bits =
+            Entropy::from_bytes_vec(bits[..entropy_nb_bytes].to_vec())?.checksum_nb_bits();
+        let nb_candidates = 1u16 << (BITS_LEN_ITERATION - checksum_nb_bits);
+
+        let supplemental_bits = supplemental_bits.unwrap_or(0);
+        if supplemental_bits >= nb_candidates {
+            return Err(BIP32Error::InvalidEntropySource(format!(
+                "the supplemental bits {} should be lower than {}",
+                supplemental_bits, nb_candidates
+            )));
+        }
+
+        (0..nb_candidates)
+            .map(|free_bits| {
+                // The checksum bits are left to 0, they are computed from the entropy
+                words_index[partial_words.len()] =
+                    (free_bits ^ supplemental_bits) << checksum_nb_bits;
+                let bits = Mnemonic::bits_from_word_index_list(&words_index);
+
+                Mnemonic::from_entropy(
+                    Entropy::from_bytes_vec(bits[..entropy_nb_bytes].to_vec())?,
+                    lang.clone(),
+                )
+            })
+            .collect()
+    }
+
     /// 
     /// The number of words that will be generate
     /// 
@@ -96,32 +353,54 @@ impl Mnemonic {
         self.mnemonic_words.iter().collect()
     }
 
+    ///
+    /// Get the language of the words
+    ///
+    pub fn get_language(&self) -> &Language {
+        &self.lang
+    }
+
+    ///
+    /// Borrow the entropy encoded by the words
+    ///
+    pub fn get_entropy(&self) -> &Entropy {
+        &self.entropy
+    }
+
     /// 
     /// Mnemonic phrase must have at least 12 words, max 24 words and have to be divisible by 3
     /// 
     pub fn is_mnemonic_sentence_valid(sentence: String) -> bool {
-        let length = sentence.split(' ').into_iter().count();
-        length >= 12 && length <= 24 && length % 3 == 0
+        Mnemonic::is_mnemonic_words_count_valid(sentence.split_whitespace().count())
+    }
+
+    pub(crate) fn is_mnemonic_words_count_valid(length: usize) -> bool {
+        (12..=24).contains(&length) && length.is_multiple_of(3)
     }
 }
 
 ///
-/// Represent the seed of the mnemonic
+/// Represent the seed of the mnemonic, wiped from memory when dropped
 /// 
+#[derive(Debug)]
 pub struct Seed {
     val: Bytes,
 }
 
 impl Seed {
     ///
-    /// Create a new seed
+    /// Create a new seed, the mnemonic phrase and the passphrase are normalized to UTF-8 NFKD
     /// 
     pub fn new(mnemonic_phrase: &String, passphrase: &Option<String>) -> Seed {
         let header = "mnemonic".to_owned();
-        let passphrase_string = passphrase.to_owned().unwrap_or(String::from(""));
+        let passphrase_string =
+            Zeroizing::new(utils::to_utf8_nfkd(passphrase.to_owned().unwrap_or_default()));
         let salt = [header.as_bytes(), passphrase_string.as_bytes()].concat();
 
-        let seed = utils::pbkdf2_hash(mnemonic_phrase.as_bytes().to_vec(), salt);
+        let seed = utils::pbkdf2_hash(
+            utils::to_utf8_nfkd(mnemonic_phrase.to_owned()).into_bytes(),
+            salt,
+        );
 
         Seed {
             val: Bytes::new(seed),
@@ -139,14 +418,20 @@ impl Seed {
 
 #[cfg(test)]
 mod tests {
-    use std::u8;
-    use crate::{language::Language, mnemonic::EntropySize, NB_BITS_IN_BYTE};
+    use std::sync::Arc;
+
+    use crate::{
+        error::BIP32Error,
+        language::{Language, Words},
+        mnemonic::EntropySize,
+        NB_BITS_IN_BYTE,
+    };
 
-    use super::{Entropy, Mnemonic};
+    use super::{Entropy, Mnemonic, Seed};
 
     // Private function to create default entropy (only 0) from entropy size
     fn generate_default_entropy(nb_bytes: usize) -> Vec<u8> {
-        (0..nb_bytes).map(|_| 0 as u8).collect::<Vec<u8>>()
+        (0..nb_bytes).map(|_| 0_u8).collect::<Vec<u8>>()
     }
 
     #[test]
@@ -293,4 +578,109 @@ mod tests {
 
         assert_eq!( mnemonic_default_128.get_phrase(), "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".to_owned());
     }
+
+    #[test]
+    fn create_mnemonic_from_phrase() {
+        let inputs = vec![
+            "00000000000000000000000000000000",
+            "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+            "808080808080808080808080808080808080808080808080",
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "68a79eaca2324873eacc50cb9c6eca8cc68ea5d936f98787c60c7ebc74e6ce7c",
+        ];
+        for entropy_hex in inputs.into_iter() {
+            let mnemonic = Mnemonic::from_entropy(
+                Entropy::from_hex(entropy_hex.to_owned()).unwrap(),
+                Language::English,
+            )
+            .unwrap();
+
+            let mnemonic_from_phrase =
+                Mnemonic::from_phrase(mnemonic.get_phrase(), Language::English).unwrap();
+
+            assert_eq!(mnemonic_from_phrase.entropy, mnemonic.entropy);
+            assert_eq!(mnemonic_from_phrase.get_phrase(), mnemonic.get_phrase());
+        }
+    }
+
+    #[test]
+    fn create_mnemonic_from_invalid_phrase_should_err() {
+        // Last word changed, the checksum doesn't match anymore
+        assert_eq!(
+            Mnemonic::from_phrase("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon".to_owned(), Language::English).err(),
+            Some(BIP32Error::InvalidChecksum)
+        );
+
+        // Word not in the english list
+        assert_eq!(
+            Mnemonic::from_phrase("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon bitcoin".to_owned(), Language::English).err(),
+            Some(BIP32Error::UnknownWord("bitcoin".to_owned()))
+        );
+
+        // Words count not valid
+        assert_eq!(
+            Mnemonic::from_phrase("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".to_owned(), Language::English).err(),
+            Some(BIP32Error::InvalidWordsCount(11))
+        );
+    }
+
+    #[test]
+    fn create_mnemonic_from_phrase_any_language() {
+        let inputs = vec![
+            Language::English,
+            Language::French,
+            Language::Japanese,
+            Language::Czech,
+        ];
+        for lang in inputs.into_iter() {
+            let mnemonic = Mnemonic::create(EntropySize::Bits128, lang).unwrap();
+            let mnemonic_from_phrase =
+                Mnemonic::from_phrase_any_language(mnemonic.get_phrase()).unwrap();
+
+            assert_eq!(mnemonic_from_phrase.entropy, mnemonic.entropy);
+        }
+    }
+
+    #[test]
+    fn create_mnemonic_from_custom_words() {
+        let english = Words::load(Language::English);
+        let reversed_words: Vec<String> = english
+            .get_words_from_index(&(0..2048).rev().collect())
+            .unwrap();
+        let custom = Language::Custom(Arc::new(Words::from_lines(&reversed_words.join("\n")).unwrap()));
+
+        let mnemonic = Mnemonic::from_entropy(
+            Entropy::from_hex("00000000000000000000000000000000".to_owned()).unwrap(),
+            custom.clone(),
+        )
+        .unwrap();
+        assert_eq!(mnemonic.get_phrase(), "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zebra");
+        assert_eq!(*mnemonic.phrase_zeroizing(), mnemonic.get_phrase());
+
+        let mnemonic_from_phrase = Mnemonic::from_phrase(mnemonic.get_phrase(), custom).unwrap();
+        assert_eq!(mnemonic_from_phrase.entropy, mnemonic.entropy);
+    }
+
+    #[test]
+    fn create_seed_from_not_normalized_passphrase() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".to_owned();
+
+        // "é" precomposed (U+00E9) and decomposed ("e" + U+0301) should give the same seed
+        let seed_precomposed = Seed::new(&phrase, &Some("caf\u{e9}".to_owned()));
+        let seed_decomposed = Seed::new(&phrase, &Some("cafe\u{301}".to_owned()));
+
+        assert_eq!(seed_precomposed.to_hex(), seed_decomposed.to_hex());
+    }
+
+    #[test]
+    fn mnemonic_and_seed_debug_are_redacted() {
+        let mnemonic = Mnemonic::from_entropy(Entropy::default(), Language::English).unwrap();
+        let seed = Seed::new(&mnemonic.get_phrase(), &None);
+
+        assert_eq!(
+            format!("{:?}", mnemonic),
+            "Mnemonic { mnemonic_words: [REDACTED; 24], entropy: Entropy { entropy: Bytes([REDACTED; 32]) } }"
+        );
+        assert_eq!(format!("{:?}", seed), "Seed { val: Bytes([REDACTED; 64]) }");
+    }
 }
@@ -0,0 +1,364 @@
+use std::{
+    collections::HashSet,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    thread,
+    time::Instant,
+};
+
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::{
+    error::BIP32Error,
+    mnemonic::{Mnemonic, Seed},
+    recovery::{Progress, RecoveryTarget},
+};
+
+/* Config */
+const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &str = "0123456789";
+const SYMBOLS: &str = " !\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+const BATCH_SIZE: u64 = 16;
+
+///
+/// Source of the candidates of a passphrase generator
+///
+#[derive(Zeroize)]
+enum Candidates {
+    List(Vec<String>),
+    Mask(Vec<Vec<char>>),
+}
+
+///
+/// Generator of passphrase candidates, each candidate is addressed by its index so the search can be
+/// split between the threads without storing all the candidates
+///
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct PassphraseGenerator {
+    candidates: Candidates,
+}
+
+impl PassphraseGenerator {
+    ///
+    /// Try each passphrase of the list
+    ///
+    pub fn dictionary(passphrases: Vec<String>) -> PassphraseGenerator {
+        PassphraseGenerator {
+            candidates: Candidates::List(passphrases),
+        }
+    }
+
+    ///
+    /// Try every passphrase matching the mask, as hashcat masks:
+    ///     ?l = lowercase letter, ?u = uppercase letter, ?d = digit, ?s = symbol (with space),
+    ///     ?a = any of them, ?? = "?", any other character is kept as is
+    ///
+    pub fn mask(mask: &str) -> Result<PassphraseGenerator, BIP32Error> {
+        let mut positions = vec![];
+        let mut chars = mask.chars();
+
+        while let Some(c) = chars.next() {
+            let charset = match c {
+                '?' => match chars.next() {
+                    Some('l') => LOWERCASE.chars().collect(),
+                    Some('u') => UPPERCASE.chars().collect(),
+                    Some('d') => DIGITS.chars().collect(),
+                    Some('s') => SYMBOLS.chars().collect(),
+                    Some('a') => [LOWERCASE, UPPERCASE, DIGITS, SYMBOLS]
+                        .concat()
+                        .chars()
+                        .collect(),
+                    Some('?') => vec!['?'],
+                    _ => return Err(BIP32Error::InvalidPassphraseMask(mask.to_owned())),
+                },
+                c => vec![c],
+            };
+            positions.push(charset);
+        }
+
+        Ok(PassphraseGenerator {
+            candidates: Candidates::Mask(positions),
+        })
+    }
+
+    ///
+    /// Try the typo variations of a known guess, from the most to the least likely:
+    /// the guess, case changes, swapped letters, missing letter, wrong letter and extra letter
+    /// (with any printable ASCII character)
+    ///
+    pub fn typos(guess: &str) -> PassphraseGenerator {
+        let guess = guess.chars().collect::<Vec<char>>();
+        let printable = (' '..='~').collect::<Vec<char>>();
+        let mut variations = vec![guess.clone()];
+
+        variations.push(guess.iter().flat_map(|c| c.to_lowercase()).collect());
+        variations.push(guess.iter().flat_map(|c| c.to_uppercase()).collect());
+        for i in 0..guess.len() {
+            let mut variation = guess.clone();
+            variation[i] = match guess[i].is_uppercase() {
+                true => guess[i].to_ascii_lowercase(),
+                false => guess[i].to_ascii_uppercase(),
+            };
+            variations.push(variation);
+        }
+        for i in 1..guess.len() {
+            let mut variation = guess.clone();
+            variation.swap(i - 1, i);
+            variations.push(variation);
+        }
+        for i in 0..guess.len() {
+            let mut variation = guess.clone();
+            variation.remove(i);
+            variations.push(variation);
+        }
+        for i in 0..guess.len() {
+            for c in printable.iter() {
+                let mut variation = guess.clone();
+                variation[i] = *c;
+                variations.push(variation);
+            }
+        }
+        for i in 0..=guess.len() {
+            for c in printable.iter() {
+                let mut variation = guess.clone();
+                variation.insert(i, *c);
+                variations.push(variation);
+            }
+        }
+
+        let mut passphrases = variations
+            .into_iter()
+            .map(|variation| variation.into_iter().collect::<String>())
+            .collect::<Vec<String>>();
+        let mut unique_passphrases = HashSet::new();
+        passphrases.retain(|passphrase| unique_passphrases.insert(passphrase.clone()));
+        unique_passphrases
+            .into_iter()
+            .for_each(|mut passphrase| passphrase.zeroize());
+
+        PassphraseGenerator::dictionary(passphrases)
+    }
+
+    ///
+    /// Number of candidates of the generator
+    ///
+    pub fn nb_candidates(&self) -> u64 {
+        match &self.candidates {
+            Candidates::List(passphrases) => passphrases.len() as u64,
+            Candidates::Mask(positions) => positions
+                .iter()
+                .fold(1u64, |nb, charset| nb.saturating_mul(charset.len() as u64)),
+        }
+    }
+
+    ///
+    /// Get the candidate at the index, the last position of a mask changes first
+    ///
+    pub fn candidate(&self, index: u64) -> Option<Zeroizing<String>> {
+        if index >= self.nb_candidates() {
+            return None;
+        }
+
+        match &self.candidates {
+            Candidates::List(passphrases) => {
+                Some(Zeroizing::new(passphrases[index as usize].to_owned()))
+            }
+            Candidates::Mask(positions) => {
+                let mut remaining = index;
+                let mut chars = Zeroizing::new(vec![' '; positions.len()]);
+                for (i, charset) in positions.iter().enumerate().rev() {
+                    chars[i] = charset[(remaining % charset.len() as u64) as usize];
+                    remaining /= charset.len() as u64;
+                }
+
+                Some(Zeroizing::new(chars.iter().collect()))
+            }
+        }
+    }
+}
+
+///
+/// Search the forgotten passphrase of a mnemonic, each candidate seed is compared with the target
+///
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct PassphraseRecovery {
+    phrase: Zeroizing<String>,
+    #[zeroize(skip)]
+    target: RecoveryTarget,
+    generator: PassphraseGenerator,
+    nb_threads: usize,
+}
+
+impl PassphraseRecovery {
+    ///
+    /// Prepare the recovery of the passphrase, the search runs on all the available cores
+    ///
+    pub fn new(
+        mnemonic: &Mnemonic,
+        target: RecoveryTarget,
+        generator: PassphraseGenerator,
+    ) -> PassphraseRecovery {
+        PassphraseRecovery {
+            phrase: mnemonic.phrase_zeroizing(),
+            target,
+            generator,
+            nb_threads: thread::available_parallelism().map_or(1, |nb| nb.get()),
+        }
+    }
+
+    pub fn with_threads(mut self, nb_threads: usize) -> PassphraseRecovery {
+        self.nb_threads = nb_threads.max(1);
+        self
+    }
+
+    ///
+    /// Number of passphrases to check, each one costs a seed computation (2048 PBKDF2 iterations)
+    ///
+    pub fn search_space(&self) -> u64 {
+        self.generator.nb_candidates()
+    }
+
+    ///
+    /// Search the passphrase on several threads, the progress callback is called from the threads.
+    /// The search stops at the first passphrase matching the target, or when the cancel flag is set
+    ///
+    pub fn run(
+        &self,
+        progress: Option<&(dyn Fn(Progress) + Sync)>,
+        cancel: Option<&AtomicBool>,
+    ) -> Result<Option<Zeroizing<String>>, BIP32Error> {
+        let next_index = AtomicU64::new(0);
+        let checked = AtomicU64::new(0);
+        // Set when a thread finds the passphrase or fails, to stop the other threads
+        let stop = AtomicBool::new(false);
+        // Set when a batch is left unchecked because of the cancel flag
+        let cancelled = AtomicBool::new(false);
+        let total = self.search_space();
+        let start = Instant::now();
+        let is_cancelled = || cancel.is_some_and(|cancel| cancel.load(Ordering::Relaxed));
+
+        let results = thread::scope(|scope| {
+            let handles = (0..self.nb_threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        while !stop.load(Ordering::Relaxed) {
+                            let first_index = next_index.fetch_add(BATCH_SIZE, Ordering::Relaxed);
+                            if first_index >= total {
+                                break;
+                            }
+                            if is_cancelled() {
+                                cancelled.store(true, Ordering::Relaxed);
+                                break;
+                            }
+
+                            let last_index = first_index.saturating_add(BATCH_SIZE).min(total);
+                            for index in first_index..last_index {
+                                match self.check(index) {
+                                    Ok(None) => {}
+                                    result => {
+                                        stop.store(true, Ordering::Relaxed);
+                                        return result;
+                                    }
+                                }
+                            }
+
+                            let nb_checked = last_index - first_index;
+                            let checked =
+                                checked.fetch_add(nb_checked, Ordering::Relaxed) + nb_checked;
+                            if let Some(progress) = progress {
+                                progress(Progress {
+                                    checked,
+                                    total,
+                                    elapsed: start.elapsed(),
+                                });
+                            }
+                        }
+
+                        Ok(None)
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("The recovery thread panicked"))
+                .collect::<Result<Vec<Option<Zeroizing<String>>>, BIP32Error>>()
+        })?;
+
+        match results.into_iter().flatten().next() {
+            Some(passphrase) => Ok(Some(passphrase)),
+            None if cancelled.load(Ordering::Relaxed) => Err(BIP32Error::RecoveryCancelled),
+            None => Ok(None),
+        }
+    }
+
+    ///
+    /// Compare the seed of the candidate at the index with the target
+    ///
+    fn check(&self, index: u64) -> Result<Option<Zeroizing<String>>, BIP32Error> {
+        let passphrase = match self.generator.candidate(index) {
+            Some(passphrase) => passphrase,
+            None => return Ok(None),
+        };
+        let seed = Seed::new(&self.phrase, &Zeroizing::new(Some(passphrase.to_string())));
+
+        match self.target.is_matching_seed(&seed)? {
+            true => Ok(Some(passphrase)),
+            false => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::BIP32Error;
+
+    use super::PassphraseGenerator;
+
+    ///
+    /// The last position of the mask changes first
+    ///
+    #[test]
+    fn generate_mask_candidates() {
+        let generator = PassphraseGenerator::mask("a?d?u??").unwrap();
+
+        assert_eq!(generator.nb_candidates(), 260);
+        assert_eq!(generator.candidate(0).unwrap().as_str(), "a0A?");
+        assert_eq!(generator.candidate(1).unwrap().as_str(), "a0B?");
+        assert_eq!(generator.candidate(26).unwrap().as_str(), "a1A?");
+        assert_eq!(generator.candidate(259).unwrap().as_str(), "a9Z?");
+        assert!(generator.candidate(260).is_none());
+        assert_eq!(PassphraseGenerator::mask("?a").unwrap().nb_candidates(), 95);
+    }
+
+    ///
+    /// Unknown placeholders should err
+    ///
+    #[test]
+    fn parse_invalid_mask_should_err() {
+        for mask in ["?x", "abc?", "?l?"] {
+            assert!(matches!(
+                PassphraseGenerator::mask(mask),
+                Err(BIP32Error::InvalidPassphraseMask(_))
+            ));
+        }
+    }
+
+    ///
+    /// The guess comes first and the variations are unique
+    ///
+    #[test]
+    fn generate_typos_candidates() {
+        let generator = PassphraseGenerator::typos("Ab");
+        let candidates = (0..generator.nb_candidates())
+            .map(|index| generator.candidate(index).unwrap().to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(candidates[0], "Ab");
+        for variation in ["ab", "AB", "bA", "A", "b", "Ac", "A b", "Abc", "xAb"] {
+            assert!(candidates.contains(&variation.to_owned()));
+        }
+        assert!(!candidates[1..].contains(&"Ab".to_owned()));
+        assert!(!candidates.contains(&"xAbc".to_owned()));
+    }
+}
@@ -0,0 +1,620 @@
+use std::{
+    collections::HashSet,
+    sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::{
+    address,
+    bip32::{DerivationPath, ExtendedPrivateKey, ExtendedPublicKey},
+    error::BIP32Error,
+    language::{Language, Words},
+    mnemonic::{Mnemonic, Seed},
+    utils, NB_WORDS,
+};
+
+/* Config */
+const MAX_UNKNOWN_WORDS: usize = 2;
+const UNKNOWN_WORD: &str = "?";
+const KEYBOARD_ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+const EDIT_COST: usize = 2;
+const NEAR_KEY_COST: usize = 1;
+
+///
+/// Known information confirming that a recovered mnemonic is the right one
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecoveryTarget {
+    /// Fingerprint of the BIP32 master key
+    Fingerprint([u8; 4]),
+    /// Extended public key (xpub) at the derivation path, usually the account path as "m/84'/0'/0'"
+    ExtendedPublicKey(DerivationPath, ExtendedPublicKey),
+    /// Mainnet P2PKH ("1...") or P2WPKH ("bc1q...") address of the key at the derivation path
+    Address(DerivationPath, String),
+}
+
+impl RecoveryTarget {
+    ///
+    /// Does the mnemonic with the passphrase match the target
+    /// The seed is computed for each call (2048 PBKDF2 iterations), it's the slow part of the recovery
+    ///
+    pub fn is_matching(
+        &self,
+        mnemonic: &Mnemonic,
+        passphrase: &Option<String>,
+    ) -> Result<bool, BIP32Error> {
+        self.is_matching_seed(&Seed::new(&mnemonic.phrase_zeroizing(), passphrase))
+    }
+
+    pub(crate) fn is_matching_seed(&self, seed: &Seed) -> Result<bool, BIP32Error> {
+        let master = ExtendedPrivateKey::from_seed(seed)?;
+
+        match self {
+            RecoveryTarget::Fingerprint(fingerprint) => Ok(master.fingerprint() == *fingerprint),
+            RecoveryTarget::ExtendedPublicKey(path, extended_public_key) => {
+                Ok(master.derive_path(path)?.to_extended_public_key() == *extended_public_key)
+            }
+            RecoveryTarget::Address(path, address) => {
+                let public_key = master.derive_path(path)?.to_extended_public_key();
+                Ok(address::is_address_of(
+                    address,
+                    &public_key.public_key_bytes(),
+                ))
+            }
+        }
+    }
+}
+
+///
+/// Progress of the search, reported after each batch of checked candidates
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    pub checked: u64,
+    pub total: u64,
+    pub elapsed: Duration,
+}
+
+impl Progress {
+    ///
+    /// Number of candidates checked per second since the start of the search
+    ///
+    pub fn throughput(&self) -> f64 {
+        self.checked as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+///
+/// Checksum valid mnemonic found by the recovery, with its distance to the words written by the user
+///
+#[derive(Debug)]
+pub struct Candidate {
+    mnemonic: Mnemonic,
+    distance: usize,
+}
+
+impl Candidate {
+    pub fn mnemonic(&self) -> &Mnemonic {
+        &self.mnemonic
+    }
+
+    ///
+    /// Sum of the typing distances between the written words and the recovered words, 0 for unknown words
+    ///
+    pub fn distance(&self) -> usize {
+        self.distance
+    }
+}
+
+///
+/// Word of the phrase to recover, the written word is kept to rank the candidates
+///
+#[derive(Clone, Zeroize)]
+enum Slot {
+    Known(u16),
+    Unknown(Option<String>),
+}
+
+///
+/// Recover a mnemonic with one or two missing or wrong words
+///     - "?" marks an unknown word, a word not in the words list is considered wrong
+///     - with one word less than a valid phrase, the lost word is searched at every position
+///     - with a valid count of known words but an invalid checksum, each word is considered wrong in turn
+/// Every checksum valid completion is returned, ranked by typing distance (edit distance where a
+/// substitution by a neighbour key of the keyboard costs less) to the written words.
+/// With a target, only the completions matching the target are returned
+///
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct WordRecovery {
+    #[zeroize(skip)]
+    lang: Language,
+    layouts: Vec<Vec<Slot>>,
+    passphrase: Option<String>,
+    #[zeroize(skip)]
+    target: Option<RecoveryTarget>,
+    nb_threads: usize,
+}
+
+impl WordRecovery {
+    ///
+    /// Prepare the recovery of the phrase, the search runs on all the available cores
+    ///
+    pub fn new(phrase: &str, lang: Language) -> Result<WordRecovery, BIP32Error> {
+        let words = Words::load(lang.clone());
+        let phrase = Zeroizing::new(utils::to_utf8_nfkd(phrase.to_owned()));
+        let written_words = Zeroizing::new(lang.split_phrase(&phrase));
+
+        let slots = written_words
+            .iter()
+            .map(|word| match word.as_str() {
+                UNKNOWN_WORD => Slot::Unknown(None),
+                word => match words.index_of(word) {
+                    Some(index) => Slot::Known(index),
+                    None => Slot::Unknown(Some(word.to_owned())),
+                },
+            })
+            .collect::<Vec<Slot>>();
+        let nb_unknown = slots
+            .iter()
+            .filter(|slot| matches!(slot, Slot::Unknown(_)))
+            .count();
+
+        let layouts = if Mnemonic::is_mnemonic_words_count_valid(slots.len()) {
+            if nb_unknown == 0 && !WordRecovery::is_checksum_valid(&slots) {
+                (0..slots.len())
+                    .map(|position| {
+                        let mut layout = slots.clone();
+                        layout[position] = Slot::Unknown(Some(written_words[position].to_owned()));
+                        layout
+                    })
+                    .collect()
+            } else {
+                vec![slots]
+            }
+        } else if Mnemonic::is_mnemonic_words_count_valid(slots.len() + 1) {
+            (0..=slots.len())
+                .map(|position| {
+                    let mut layout = slots.clone();
+                    layout.insert(position, Slot::Unknown(None));
+                    layout
+                })
+                .collect()
+        } else {
+            return Err(BIP32Error::InvalidWordsCount(slots.len()));
+        };
+
+        let nb_unknown = layouts
+            .iter()
+            .map(|layout| WordRecovery::unknown_positions(layout).len())
+            .max()
+            .unwrap_or(0);
+        if nb_unknown > MAX_UNKNOWN_WORDS {
+            return Err(BIP32Error::TooManyUnknownWords(nb_unknown));
+        }
+
+        Ok(WordRecovery {
+            lang,
+            layouts,
+            passphrase: None,
+            target: None,
+            nb_threads: thread::available_parallelism().map_or(1, |nb| nb.get()),
+        })
+    }
+
+    ///
+    /// Passphrase used to compare the candidates with the target
+    ///
+    pub fn with_passphrase(mut self, passphrase: Option<String>) -> WordRecovery {
+        self.passphrase = passphrase;
+        self
+    }
+
+    ///
+    /// Only keep the candidates matching the target
+    ///
+    pub fn with_target(mut self, target: RecoveryTarget) -> WordRecovery {
+        self.target = Some(target);
+        self
+    }
+
+    pub fn with_threads(mut self, nb_threads: usize) -> WordRecovery {
+        self.nb_threads = nb_threads.max(1);
+        self
+    }
+
+    ///
+    /// Number of phrases to check (2048 per unknown word and per possible position of the lost word)
+    /// About 1 / 2^CS phrases have a valid checksum, the target is only checked for them
+    ///
+    pub fn search_space(&self) -> u64 {
+        self.layouts
+            .iter()
+            .map(|layout| {
+                (NB_WORDS as u64).pow(WordRecovery::unknown_positions(layout).len() as u32)
+            })
+            .sum()
+    }
+
+    ///
+    /// Search the candidates on several threads, the progress callback is called from the threads
+    ///
+    pub fn run(
+        &self,
+        progress: Option<&(dyn Fn(Progress) + Sync)>,
+    ) -> Result<Vec<Candidate>, BIP32Error> {
+        // A batch is a layout with its first unknown word fixed
+        let batches = self
+            .layouts
+            .iter()
+            .flat_map(|layout| {
+                let nb_first_words = match WordRecovery::unknown_positions(layout).is_empty() {
+                    true => 1,
+                    false => NB_WORDS as u16,
+                };
+                (0..nb_first_words).map(move |first_word| (layout, first_word))
+            })
+            .collect::<Vec<(&Vec<Slot>, u16)>>();
+        let next_batch = AtomicUsize::new(0);
+        let checked = AtomicU64::new(0);
+        let failed = AtomicBool::new(false);
+        let total = self.search_space();
+        let start = Instant::now();
+
+        let results = thread::scope(|scope| {
+            let handles = (0..self.nb_threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut candidates = vec![];
+
+                        while let Some((layout, first_word)) =
+                            batches.get(next_batch.fetch_add(1, Ordering::Relaxed))
+                        {
+                            // Another thread failed, its error is returned once the threads are joined
+                            if failed.load(Ordering::Relaxed) {
+                                break;
+                            }
+
+                            let nb_checked =
+                                match self.search_batch(layout, *first_word, &mut candidates) {
+                                    Ok(nb_checked) => nb_checked,
+                                    Err(error) => {
+                                        failed.store(true, Ordering::Relaxed);
+                                        return Err(error);
+                                    }
+                                };
+
+                            let checked =
+                                checked.fetch_add(nb_checked, Ordering::Relaxed) + nb_checked;
+                            if let Some(progress) = progress {
+                                progress(Progress {
+                                    checked,
+                                    total,
+                                    elapsed: start.elapsed(),
+                                });
+                            }
+                        }
+
+                        Ok(candidates)
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("The recovery thread panicked"))
+                .collect::<Result<Vec<Vec<Candidate>>, BIP32Error>>()
+        })?;
+
+        let mut candidates = results.into_iter().flatten().collect::<Vec<Candidate>>();
+        candidates.sort_by(|a, b| {
+            a.distance
+                .cmp(&b.distance)
+                .then_with(|| a.mnemonic.get_words().cmp(&b.mnemonic.get_words()))
+        });
+        // The lost word inserted before or after the same word gives the same phrase
+        candidates.dedup_by(|a, b| a.mnemonic.get_words() == b.mnemonic.get_words());
+
+        Ok(candidates)
+    }
+
+    ///
+    /// Check every phrase of the layout with the first unknown word fixed, return the number of checked phrases
+    ///
+    fn search_batch(
+        &self,
+        layout: &[Slot],
+        first_word: u16,
+        candidates: &mut Vec<Candidate>,
+    ) -> Result<u64, BIP32Error> {
+        let unknown_positions = WordRecovery::unknown_positions(layout);
+        let mut words_index = Zeroizing::new(
+            layout
+                .iter()
+                .map(|slot| match slot {
+                    Slot::Known(index) => *index,
+                    Slot::Unknown(_) => 0,
+                })
+                .collect::<Vec<u16>>(),
+        );
+        if let Some(position) = unknown_positions.first() {
+            words_index[*position] = first_word;
+        }
+
+        let nb_second_words = match unknown_positions.get(1) {
+            Some(_) => NB_WORDS as u16,
+            None => 1,
+        };
+        for second_word in 0..nb_second_words {
+            if let Some(position) = unknown_positions.get(1) {
+                words_index[*position] = second_word;
+            }
+
+            let entropy = match Mnemonic::entropy_from_word_index_list(&words_index) {
+                Ok(entropy) => entropy,
+                Err(_) => continue,
+            };
+            let mnemonic = Mnemonic::from_entropy(entropy, self.lang.clone())?;
+
+            if let Some(target) = &self.target {
+                if !target.is_matching(&mnemonic, &self.passphrase)? {
+                    continue;
+                }
+            }
+
+            let distance = unknown_positions
+                .iter()
+                .map(|position| match &layout[*position] {
+                    Slot::Unknown(Some(written_word)) => {
+                        typing_distance(written_word, mnemonic.get_words()[*position])
+                    }
+                    _ => 0,
+                })
+                .sum();
+            candidates.push(Candidate { mnemonic, distance });
+        }
+
+        Ok(nb_second_words as u64)
+    }
+
+    fn unknown_positions(layout: &[Slot]) -> Vec<usize> {
+        layout
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| matches!(slot, Slot::Unknown(_)))
+            .map(|(position, _)| position)
+            .collect()
+    }
+
+    fn is_checksum_valid(slots: &[Slot]) -> bool {
+        let words_index = Zeroizing::new(
+            slots
+                .iter()
+                .filter_map(|slot| match slot {
+                    Slot::Known(index) => Some(*index),
+                    Slot::Unknown(_) => None,
+                })
+                .collect::<Vec<u16>>(),
+        );
+
+        Mnemonic::entropy_from_word_index_list(&words_index).is_ok()
+    }
+}
+
+///
+/// Reordering of the written words giving a checksum valid mnemonic, from the most to the least plausible
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Transposition {
+    /// The word at the position and the next one are swapped
+    AdjacentSwap(usize),
+    /// The grid backup with this number of columns has been read column by column instead of row by row
+    ColumnReading(usize),
+    /// The rows of the grid backup with this number of columns have been read from the last to the first
+    ReversedRows(usize),
+    /// The words at the two positions are swapped
+    PairSwap(usize, usize),
+}
+
+impl Transposition {
+    ///
+    /// Kind of transposition first, then the closest swapped words first
+    ///
+    fn rank(&self) -> (u8, usize) {
+        match self {
+            Transposition::AdjacentSwap(_) => (0, 1),
+            Transposition::ColumnReading(nb_columns) => (1, *nb_columns),
+            Transposition::ReversedRows(nb_columns) => (2, *nb_columns),
+            Transposition::PairSwap(first, second) => (3, second - first),
+        }
+    }
+
+    ///
+    /// Position in the written phrase of each word of the reordered phrase
+    ///
+    fn positions(&self, nb_words: usize) -> Vec<usize> {
+        let mut positions = (0..nb_words).collect::<Vec<usize>>();
+
+        match *self {
+            Transposition::AdjacentSwap(position) => positions.swap(position, position + 1),
+            Transposition::PairSwap(first, second) => positions.swap(first, second),
+            Transposition::ColumnReading(nb_columns) => {
+                let nb_rows = nb_words / nb_columns;
+                for (position, written_position) in positions.iter_mut().enumerate() {
+                    let (row, column) = (position / nb_columns, position % nb_columns);
+                    *written_position = column * nb_rows + row;
+                }
+            }
+            Transposition::ReversedRows(nb_columns) => {
+                let nb_rows = nb_words / nb_columns;
+                for (position, written_position) in positions.iter_mut().enumerate() {
+                    let (row, column) = (position / nb_columns, position % nb_columns);
+                    *written_position = (nb_rows - 1 - row) * nb_columns + column;
+                }
+            }
+        }
+
+        positions
+    }
+}
+
+///
+/// Checksum valid mnemonic found by reordering the written words
+///
+#[derive(Debug)]
+pub struct TranspositionCandidate {
+    mnemonic: Mnemonic,
+    transposition: Transposition,
+}
+
+impl TranspositionCandidate {
+    pub fn mnemonic(&self) -> &Mnemonic {
+        &self.mnemonic
+    }
+
+    pub fn transposition(&self) -> Transposition {
+        self.transposition
+    }
+}
+
+///
+/// Recover a mnemonic whose words are all correct but not in the right order (invalid checksum).
+/// Search the adjacent swaps, the column by column and the reversed rows readings of the grid backups
+/// (2, 3, 4... columns), then any swap of two words. The checksum valid reorderings are returned from the
+/// most to the least plausible, a phrase reachable by several transpositions is only returned once.
+/// A phrase with a valid checksum is refused, its words are already in a valid order
+///
+pub fn recover_transpositions(
+    phrase: &str,
+    lang: Language,
+) -> Result<Vec<TranspositionCandidate>, BIP32Error> {
+    let words = Words::load(lang.clone());
+    let phrase = Zeroizing::new(utils::to_utf8_nfkd(phrase.to_owned()));
+    let written_words = Zeroizing::new(lang.split_phrase(&phrase));
+    let nb_words = written_words.len();
+
+    if !Mnemonic::is_mnemonic_words_count_valid(nb_words) {
+        return Err(BIP32Error::InvalidWordsCount(nb_words));
+    }
+    let written_index = Zeroizing::new(words.get_index_from_words(&written_words)?);
+    if Mnemonic::entropy_from_word_index_list(&written_index).is_ok() {
+        return Err(BIP32Error::ChecksumAlreadyValid);
+    }
+
+    let mut transpositions = (0..nb_words - 1)
+        .map(Transposition::AdjacentSwap)
+        .collect::<Vec<Transposition>>();
+    for nb_columns in (2..nb_words).filter(|nb_columns| nb_words.is_multiple_of(*nb_columns)) {
+        transpositions.push(Transposition::ColumnReading(nb_columns));
+        transpositions.push(Transposition::ReversedRows(nb_columns));
+    }
+    for first in 0..nb_words {
+        for second in first + 2..nb_words {
+            transpositions.push(Transposition::PairSwap(first, second));
+        }
+    }
+    transpositions.sort_by_key(Transposition::rank);
+
+    let mut candidates: Vec<TranspositionCandidate> = vec![];
+    let mut found_indexes: HashSet<Vec<u16>> = HashSet::new();
+    for transposition in transpositions {
+        let mut words_index = transposition
+            .positions(nb_words)
+            .iter()
+            .map(|position| written_index[*position])
+            .collect::<Vec<u16>>();
+
+        match Mnemonic::entropy_from_word_index_list(&words_index) {
+            Ok(entropy) if !found_indexes.contains(&words_index) => {
+                found_indexes.insert(words_index);
+                candidates.push(TranspositionCandidate {
+                    mnemonic: Mnemonic::from_entropy(entropy, lang.clone())?,
+                    transposition,
+                });
+            }
+            _ => words_index.zeroize(),
+        }
+    }
+    found_indexes
+        .into_iter()
+        .for_each(|mut words_index| words_index.zeroize());
+
+    Ok(candidates)
+}
+
+///
+/// Damerau-Levenshtein distance where the substitution by a neighbour key of a QWERTY keyboard and the
+/// transposition of two letters cost half of the other edits
+///
+pub fn typing_distance(written: &str, word: &str) -> usize {
+    utils::weighted_damerau_levenshtein(written, word, EDIT_COST, NEAR_KEY_COST, |a, b| {
+        match are_neighbour_keys(a, b) {
+            true => NEAR_KEY_COST,
+            false => EDIT_COST,
+        }
+    })
+}
+
+///
+/// Neighbour keys on a QWERTY keyboard, each row is shifted half a key to the right of the previous one
+///
+fn are_neighbour_keys(a: char, b: char) -> bool {
+    let key_position = |c: char| {
+        KEYBOARD_ROWS.iter().enumerate().find_map(|(row, keys)| {
+            keys.find(c.to_ascii_lowercase())
+                .map(|column| (row as isize, column as isize))
+        })
+    };
+
+    match (key_position(a), key_position(b)) {
+        (Some((row_a, column_a)), Some((row_b, column_b))) => match row_b - row_a {
+            0 => (column_a - column_b).abs() == 1,
+            1 => column_b == column_a || column_b == column_a - 1,
+            -1 => column_b == column_a || column_b == column_a + 1,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{are_neighbour_keys, typing_distance};
+
+    ///
+    /// Neighbour keys are on the same row or on the rows above and below
+    ///
+    #[test]
+    fn find_neighbour_keys() {
+        assert!(are_neighbour_keys('a', 's'));
+        assert!(are_neighbour_keys('a', 'q'));
+        assert!(are_neighbour_keys('a', 'w'));
+        assert!(are_neighbour_keys('s', 'z'));
+        assert!(are_neighbour_keys('g', 'b'));
+        assert!(!are_neighbour_keys('a', 'e'));
+        assert!(!are_neighbour_keys('q', 'p'));
+        assert!(!are_neighbour_keys('a', 'é'));
+    }
+
+    ///
+    /// Typos on neighbour keys and swapped letters are closer than other edits
+    ///
+    #[test]
+    fn compute_typing_distance() {
+        let list = vec![
+            ("about", "about", 0),
+            ("abiut", "about", 1),
+            ("abxut", "about", 2),
+            ("abuot", "about", 1),
+            ("abot", "about", 2),
+            ("abouts", "about", 2),
+            ("", "zoo", 6),
+        ];
+
+        for (written, word, distance) in list {
+            assert_eq!(typing_distance(written, word), distance);
+        }
+    }
+}
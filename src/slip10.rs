@@ -0,0 +1,317 @@
+use std::fmt::Debug;
+
+use ed25519_dalek::SigningKey;
+use p256::{
+    elliptic_curve::{sec1::ToEncodedPoint, PrimeField},
+    NonZeroScalar, Scalar, SecretKey,
+};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::{
+    bip32::{self, ChildNumber, DerivationPath, MAX_SEED_LEN, MIN_SEED_LEN},
+    entropy::Bytes,
+    error::BIP32Error,
+    mnemonic::Seed,
+};
+
+///
+/// Curves supported by the SLIP-0010 derivation
+/// From documentation (<https://github.com/satoshilabs/slips/blob/master/slip-0010.md>)
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Curve {
+    /// Only hardened children can be derived
+    Ed25519,
+    /// Also known as secp256r1 or P-256
+    Nist256p1,
+}
+
+impl Curve {
+    ///
+    /// Key of the HMAC used to create the master key
+    ///
+    fn master_key_hmac(&self) -> &'static [u8] {
+        match self {
+            Curve::Ed25519 => b"ed25519 seed",
+            Curve::Nist256p1 => b"Nist256p1 seed",
+        }
+    }
+}
+
+///
+/// SLIP-0010 extended private key, the private key and the chain code are wiped from memory when dropped
+///
+#[derive(Clone)]
+pub struct Slip10Key {
+    curve: Curve,
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: ChildNumber,
+    chain_code: [u8; 32],
+    private_key: [u8; 32],
+}
+
+impl Slip10Key {
+    ///
+    /// Create the master key of the curve from the mnemonic seed
+    ///
+    pub fn from_seed(seed: &Seed, curve: Curve) -> Result<Slip10Key, BIP32Error> {
+        Slip10Key::new_master(seed.get_bytes().as_ref(), curve)
+    }
+
+    ///
+    /// Create the master key from seed bytes (between 128 and 512 bits)
+    /// From documentation (<https://github.com/satoshilabs/slips/blob/master/slip-0010.md#master-key-generation>)
+    ///     Calculate I = HMAC-SHA512(Key = Curve, Data = S)
+    ///     Split I into two 32-byte sequences, IL and IR.
+    ///     Use parse256(IL) as secret key, and IR as chain code.
+    ///     If curve is not ed25519 and IL is 0 or ≥ n (invalid key):
+    ///         Set S := I and continue at step 1.
+    ///
+    pub fn new_master(seed: &[u8], curve: Curve) -> Result<Slip10Key, BIP32Error> {
+        if seed.len() < MIN_SEED_LEN || seed.len() > MAX_SEED_LEN {
+            return Err(BIP32Error::InvalidSeedLength(seed.len()));
+        }
+
+        let mut data = Zeroizing::new(seed.to_vec());
+        let (private_key, chain_code) = loop {
+            let (private_key, chain_code) =
+                bip32::hmac_sha512_split(curve.master_key_hmac(), &data)?;
+
+            if curve == Curve::Ed25519 || SecretKey::from_bytes(private_key.as_ref().into()).is_ok()
+            {
+                break (private_key, chain_code);
+            }
+
+            data.clear();
+            data.extend_from_slice(private_key.as_ref());
+            data.extend_from_slice(&chain_code);
+        };
+
+        Ok(Slip10Key {
+            curve,
+            depth: 0,
+            parent_fingerprint: [0; 4],
+            child_number: ChildNumber::Normal(0),
+            chain_code,
+            private_key: *private_key,
+        })
+    }
+
+    ///
+    /// Derive the child private key, ed25519 only supports hardened children
+    ///
+    pub fn derive_child(&self, child_number: ChildNumber) -> Result<Slip10Key, BIP32Error> {
+        child_number.check()?;
+        if self.curve == Curve::Ed25519 && !child_number.is_hardened() {
+            return Err(BIP32Error::NonHardenedDerivation(child_number.to_index()));
+        }
+
+        let mut data = Zeroizing::new(Vec::with_capacity(37));
+        if child_number.is_hardened() {
+            data.push(0);
+            data.extend_from_slice(&self.private_key);
+        } else {
+            data.extend_from_slice(&self.public_key());
+        }
+        data.extend_from_slice(&child_number.to_index().to_be_bytes());
+
+        let (private_key, chain_code) = match self.curve {
+            Curve::Ed25519 => {
+                let (private_key, chain_code) = bip32::hmac_sha512_split(&self.chain_code, &data)?;
+                (*private_key, chain_code)
+            }
+            Curve::Nist256p1 => self.derive_nist256p1_child(&mut data, child_number)?,
+        };
+
+        Ok(Slip10Key {
+            curve: self.curve,
+            depth: self
+                .depth
+                .checked_add(1)
+                .ok_or(BIP32Error::MaxDepthExceeded)?,
+            parent_fingerprint: self.fingerprint(),
+            child_number,
+            chain_code,
+            private_key,
+        })
+    }
+
+    ///
+    /// Derive all the children of the path, the current key is considered as "m"
+    ///
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Slip10Key, BIP32Error> {
+        path.as_slice()
+            .iter()
+            .try_fold(self.clone(), |key, child_number| {
+                key.derive_child(*child_number)
+            })
+    }
+
+    ///
+    /// Get the 33 bytes public key
+    /// ed25519 public key is prefixed by 0x00, nist256p1 public key is compressed
+    ///
+    pub fn public_key(&self) -> Vec<u8> {
+        match self.curve {
+            Curve::Ed25519 => {
+                let mut public_key = vec![0];
+                public_key.extend_from_slice(
+                    SigningKey::from_bytes(&self.private_key)
+                        .verifying_key()
+                        .as_bytes(),
+                );
+                public_key
+            }
+            Curve::Nist256p1 => SecretKey::from_bytes(&self.private_key.into())
+                .expect("The private key is checked during the derivation")
+                .public_key()
+                .to_encoded_point(true)
+                .as_bytes()
+                .to_vec(),
+        }
+    }
+
+    ///
+    /// First 4 bytes of the public key identifier
+    ///
+    pub fn fingerprint(&self) -> [u8; 4] {
+        bip32::fingerprint(&self.public_key())
+    }
+
+    ///
+    /// Get the 32 bytes private key
+    ///
+    pub fn private_key(&self) -> Bytes {
+        Bytes::new(self.private_key.to_vec())
+    }
+
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    pub fn curve(&self) -> Curve {
+        self.curve
+    }
+
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    pub fn child_number(&self) -> ChildNumber {
+        self.child_number
+    }
+
+    ///
+    /// ki = parse256(IL) + kpar (mod n)
+    /// If parse256(IL) ≥ n or ki = 0 (invalid key):
+    ///     Let I = HMAC-SHA512(Key = cpar, Data = 0x01 || IR || ser32(i)) and restart
+    ///
+    fn derive_nist256p1_child(
+        &self,
+        data: &mut Vec<u8>,
+        child_number: ChildNumber,
+    ) -> Result<([u8; 32], [u8; 32]), BIP32Error> {
+        let parent = Scalar::from_repr(self.private_key.into()).unwrap();
+
+        loop {
+            let (tweak, chain_code) = bip32::hmac_sha512_split(&self.chain_code, data)?;
+
+            let private_key =
+                Option::<Scalar>::from(Scalar::from_repr((*tweak).into())).and_then(|tweak| {
+                    Option::<NonZeroScalar>::from(NonZeroScalar::new(tweak + parent))
+                });
+            if let Some(private_key) = private_key {
+                return Ok((private_key.to_repr().into(), chain_code));
+            }
+
+            data.clear();
+            data.push(1);
+            data.extend_from_slice(&chain_code);
+            data.extend_from_slice(&child_number.to_index().to_be_bytes());
+        }
+    }
+}
+
+impl Drop for Slip10Key {
+    fn drop(&mut self) {
+        self.chain_code.zeroize();
+        self.private_key.zeroize();
+    }
+}
+
+impl PartialEq for Slip10Key {
+    fn eq(&self, other: &Self) -> bool {
+        self.curve == other.curve
+            && self.depth == other.depth
+            && self.parent_fingerprint == other.parent_fingerprint
+            && self.child_number == other.child_number
+            && self.chain_code == other.chain_code
+            && self.private_key == other.private_key
+    }
+}
+
+///
+/// Never display the keys, to avoid leaking them into the logs
+///
+impl Debug for Slip10Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Slip10Key")
+            .field("curve", &self.curve)
+            .field("depth", &self.depth)
+            .field("parent_fingerprint", &hex::encode(self.parent_fingerprint))
+            .field("child_number", &self.child_number)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bip32::ChildNumber, error::BIP32Error};
+
+    use super::{Curve, Slip10Key};
+
+    ///
+    /// ed25519 only supports hardened derivation
+    ///
+    #[test]
+    fn derive_ed25519_normal_child_should_err() {
+        let master = Slip10Key::new_master(&[0; 16], Curve::Ed25519).unwrap();
+
+        assert_eq!(
+            master.derive_child(ChildNumber::Normal(3)),
+            Err(BIP32Error::NonHardenedDerivation(3))
+        );
+        assert!(master.derive_child(ChildNumber::Hardened(3)).is_ok());
+    }
+
+    ///
+    /// nist256p1 supports both hardened and normal derivation
+    ///
+    #[test]
+    fn derive_nist256p1_normal_child() {
+        let master = Slip10Key::new_master(&[0; 16], Curve::Nist256p1).unwrap();
+
+        assert!(master.derive_child(ChildNumber::Normal(3)).is_ok());
+        assert_ne!(
+            master.derive_child(ChildNumber::Normal(3)).unwrap(),
+            master.derive_child(ChildNumber::Hardened(3)).unwrap()
+        );
+    }
+
+    ///
+    /// The keys are not displayed
+    ///
+    #[test]
+    fn debug_should_not_display_keys() {
+        let master = Slip10Key::new_master(&[0; 16], Curve::Ed25519).unwrap();
+        let debug = format!("{:?}", master);
+
+        assert!(!debug.contains(&hex::encode(master.private_key())));
+        assert!(!debug.contains(&hex::encode(master.chain_code())));
+    }
+}
@@ -0,0 +1,896 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use hmac::{Hmac, Mac};
+use rand::rngs::OsRng;
+use rand_core::CryptoRngCore;
+use sha2::Sha256;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::{
+    entropy::{Bytes, Entropy},
+    error::BIP32Error,
+    NB_BITS_IN_BYTE,
+};
+
+///
+/// SLIP-39 words list (1024 words), generated by the build script
+///
+mod words_list {
+    include!(concat!(env!("OUT_DIR"), "/slip39_words.rs"));
+}
+
+/* Config */
+const RADIX_BITS: usize = 10;
+const RADIX_MASK: u32 = (1 << RADIX_BITS) - 1;
+const METADATA_WORDS: usize = 7;
+const CHECKSUM_WORDS: usize = 3;
+const MIN_MNEMONIC_WORDS: usize = 20;
+const MIN_MASTER_SECRET_LEN: usize = 16;
+const MAX_SHARE_COUNT: u8 = 16;
+const MAX_ITERATION_EXPONENT: u8 = 15;
+const DIGEST_LEN: usize = 4;
+const DIGEST_INDEX: u8 = 254;
+const SECRET_INDEX: u8 = 255;
+const BASE_ITERATION_COUNT: u32 = 10000;
+const ROUND_COUNT: u8 = 4;
+const CUSTOMIZATION_STRING: &[u8] = b"shamir";
+const CUSTOMIZATION_STRING_EXTENDABLE: &[u8] = b"shamir_extendable";
+const RS1024_GENERATOR: [u32; 10] = [
+    0xE0E040, 0x1C1C080, 0x3838100, 0x7070200, 0xE0E0009, 0x1C0C2412, 0x38086C24, 0x3090FC48,
+    0x21B1F890, 0x3F3F120,
+];
+
+///
+/// Exponential and logarithm tables of GF(256) with the Rijndael polynomial x^8 + x^4 + x^3 + x + 1,
+/// the generator is x + 1
+///
+const GF256: ([u8; 255], [u8; 256]) = gf256_tables();
+
+const fn gf256_tables() -> ([u8; 255], [u8; 256]) {
+    let mut exp = [0u8; 255];
+    let mut log = [0u8; 256];
+    let mut poly: u16 = 1;
+    let mut i = 0;
+    while i < 255 {
+        exp[i] = poly as u8;
+        log[poly as usize] = i as u8;
+        poly = (poly << 1) ^ poly;
+        if poly & 0x100 != 0 {
+            poly ^= 0x11B;
+        }
+        i += 1;
+    }
+    (exp, log)
+}
+
+///
+/// Index (x coordinate) and value of a share of a secret
+///
+type SecretShare = (u8, Zeroizing<Vec<u8>>);
+
+///
+/// Threshold and count of the member shares of a group, like "2 of 3"
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GroupSpec {
+    pub member_threshold: u8,
+    pub member_count: u8,
+}
+
+impl GroupSpec {
+    pub fn new(member_threshold: u8, member_count: u8) -> GroupSpec {
+        GroupSpec {
+            member_threshold,
+            member_count,
+        }
+    }
+}
+
+///
+/// One share of the master secret, wiped from memory when dropped
+///
+#[derive(Debug, Clone, PartialEq, Zeroize, ZeroizeOnDrop)]
+pub struct Share {
+    identifier: u16,
+    extendable: bool,
+    iteration_exponent: u8,
+    group_index: u8,
+    group_threshold: u8,
+    group_count: u8,
+    member_index: u8,
+    member_threshold: u8,
+    value: Bytes,
+}
+
+impl Share {
+    ///
+    /// Decode a share from its mnemonic, checking the RS1024 checksum and the padding
+    ///
+    pub fn from_mnemonic(mnemonic: &str) -> Result<Share, BIP32Error> {
+        let words_index = Zeroizing::new(
+            mnemonic
+                .split_whitespace()
+                .map(|word| word_index(&word.to_lowercase()))
+                .collect::<Result<Vec<u16>, BIP32Error>>()?,
+        );
+
+        let nb_words = words_index.len();
+        if nb_words < MIN_MNEMONIC_WORDS || (RADIX_BITS * (nb_words - METADATA_WORDS)) % 16 > 8 {
+            return Err(BIP32Error::InvalidWordsCount(nb_words));
+        }
+
+        // Identifier (15 bits) || extendable flag (1 bit) || iteration exponent (4 bits)
+        let id_exp = ((words_index[0] as u32) << RADIX_BITS) | words_index[1] as u32;
+        let identifier = (id_exp >> 5) as u16;
+        let extendable = (id_exp >> 4) & 1 == 1;
+        let iteration_exponent = (id_exp & 0xF) as u8;
+
+        if !rs1024_verify_checksum(customization_string(extendable), &words_index) {
+            return Err(BIP32Error::InvalidShareChecksum);
+        }
+
+        // Group index || group threshold - 1 || group count - 1 || member index || member threshold - 1 (4 bits each)
+        let params = ((words_index[2] as u32) << RADIX_BITS) | words_index[3] as u32;
+        let share = Share {
+            identifier,
+            extendable,
+            iteration_exponent,
+            group_index: (params >> 16) as u8 & 0xF,
+            group_threshold: ((params >> 12) as u8 & 0xF) + 1,
+            group_count: ((params >> 8) as u8 & 0xF) + 1,
+            member_index: (params >> 4) as u8 & 0xF,
+            member_threshold: (params as u8 & 0xF) + 1,
+            value: Bytes::new(words_to_bytes(&words_index[4..nb_words - CHECKSUM_WORDS])?.to_vec()),
+        };
+
+        if share.group_threshold > share.group_count {
+            return Err(BIP32Error::InvalidShare(format!(
+                "the group threshold ({}) is greater than the group count ({})",
+                share.group_threshold, share.group_count
+            )));
+        }
+
+        Ok(share)
+    }
+
+    ///
+    /// Get the share mnemonic words
+    ///
+    pub fn get_words(&self) -> Vec<String> {
+        let id_exp = ((self.identifier as u32) << 5)
+            | ((self.extendable as u32) << 4)
+            | self.iteration_exponent as u32;
+        let params = ((self.group_index as u32) << 16)
+            | ((self.group_threshold as u32 - 1) << 12)
+            | ((self.group_count as u32 - 1) << 8)
+            | ((self.member_index as u32) << 4)
+            | (self.member_threshold as u32 - 1);
+
+        let mut words_index = Zeroizing::new(vec![
+            (id_exp >> RADIX_BITS) as u16,
+            (id_exp & RADIX_MASK) as u16,
+            (params >> RADIX_BITS) as u16,
+            (params & RADIX_MASK) as u16,
+        ]);
+        words_index.extend(bytes_to_words(self.value.as_ref()).iter());
+        let checksum = rs1024_create_checksum(customization_string(self.extendable), &words_index);
+        words_index.extend_from_slice(&checksum);
+
+        words_index
+            .iter()
+            .map(|index| words_list::SLIP39[*index as usize].to_owned())
+            .collect()
+    }
+
+    ///
+    /// Get the share mnemonic phrase
+    ///
+    pub fn get_phrase(&self) -> String {
+        self.get_words().join(" ")
+    }
+
+    pub fn identifier(&self) -> u16 {
+        self.identifier
+    }
+
+    pub fn extendable(&self) -> bool {
+        self.extendable
+    }
+
+    pub fn iteration_exponent(&self) -> u8 {
+        self.iteration_exponent
+    }
+
+    pub fn group_index(&self) -> u8 {
+        self.group_index
+    }
+
+    pub fn group_threshold(&self) -> u8 {
+        self.group_threshold
+    }
+
+    pub fn group_count(&self) -> u8 {
+        self.group_count
+    }
+
+    pub fn member_index(&self) -> u8 {
+        self.member_index
+    }
+
+    pub fn member_threshold(&self) -> u8 {
+        self.member_threshold
+    }
+}
+
+impl Display for Share {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Zeroizing::new(self.get_phrase()).as_str())
+    }
+}
+
+///
+/// Split the entropy into groups of shares, see `generate_shares`
+///
+pub fn generate_shares_from_entropy(
+    group_threshold: u8,
+    groups: &[GroupSpec],
+    entropy: &Entropy,
+    passphrase: &str,
+    extendable: bool,
+    iteration_exponent: u8,
+) -> Result<Vec<Vec<Share>>, BIP32Error> {
+    generate_shares_from_entropy_with_rng(
+        group_threshold,
+        groups,
+        entropy,
+        passphrase,
+        extendable,
+        iteration_exponent,
+        &mut OsRng,
+    )
+}
+
+///
+/// Split the entropy into groups of shares with the given cryptographically secure RNG, see `generate_shares_with_rng`
+///
+pub fn generate_shares_from_entropy_with_rng(
+    group_threshold: u8,
+    groups: &[GroupSpec],
+    entropy: &Entropy,
+    passphrase: &str,
+    extendable: bool,
+    iteration_exponent: u8,
+    rng: &mut dyn CryptoRngCore,
+) -> Result<Vec<Vec<Share>>, BIP32Error> {
+    generate_shares_with_rng(
+        group_threshold,
+        groups,
+        entropy.entropy.as_ref(),
+        passphrase,
+        extendable,
+        iteration_exponent,
+        rng,
+    )
+}
+
+///
+/// SLIP-39 Shamir's Secret-Sharing for mnemonic codes
+/// From documentation (<https://github.com/satoshilabs/slips/blob/master/slip-0039.md>)
+/// Encrypt the master secret with the passphrase, then split it into groups of shares.
+/// The master secret can be recovered from `group_threshold` groups, each with `member_threshold` shares.
+/// The master secret must be at least 128 bits and its length must be even, the passphrase must be printable ASCII.
+/// The PBKDF2 iterations count of the encryption is 10000 * 2^iteration_exponent.
+/// The identifier and the random parts of the shares come from the OS cryptographically secure RNG
+///
+pub fn generate_shares(
+    group_threshold: u8,
+    groups: &[GroupSpec],
+    master_secret: &[u8],
+    passphrase: &str,
+    extendable: bool,
+    iteration_exponent: u8,
+) -> Result<Vec<Vec<Share>>, BIP32Error> {
+    generate_shares_with_rng(
+        group_threshold,
+        groups,
+        master_secret,
+        passphrase,
+        extendable,
+        iteration_exponent,
+        &mut OsRng,
+    )
+}
+
+///
+/// Split the master secret into groups of shares as `generate_shares`, the identifier and the random parts
+/// of the shares come from the given cryptographically secure RNG (HSM, deterministic RNG for the tests...)
+///
+pub fn generate_shares_with_rng(
+    group_threshold: u8,
+    groups: &[GroupSpec],
+    master_secret: &[u8],
+    passphrase: &str,
+    extendable: bool,
+    iteration_exponent: u8,
+    rng: &mut dyn CryptoRngCore,
+) -> Result<Vec<Vec<Share>>, BIP32Error> {
+    if master_secret.len() < MIN_MASTER_SECRET_LEN || !master_secret.len().is_multiple_of(2) {
+        return Err(BIP32Error::InvalidMasterSecretLength(master_secret.len()));
+    }
+    check_passphrase(passphrase)?;
+
+    if iteration_exponent > MAX_ITERATION_EXPONENT {
+        return Err(BIP32Error::InvalidSharingParameters(format!(
+            "the iteration exponent ({}) must be lower than {}",
+            iteration_exponent,
+            MAX_ITERATION_EXPONENT + 1
+        )));
+    }
+    if groups.len() > MAX_SHARE_COUNT as usize {
+        return Err(BIP32Error::InvalidSharingParameters(format!(
+            "the number of groups ({}) must not exceed {}",
+            groups.len(),
+            MAX_SHARE_COUNT
+        )));
+    }
+    if let Some(group) = groups.iter().find(|group| group.member_count > MAX_SHARE_COUNT) {
+        return Err(BIP32Error::InvalidSharingParameters(format!(
+            "the number of members ({}) must not exceed {}",
+            group.member_count, MAX_SHARE_COUNT
+        )));
+    }
+    if group_threshold as usize > groups.len() {
+        return Err(BIP32Error::InvalidSharingParameters(format!(
+            "the group threshold ({}) must not exceed the number of groups ({})",
+            group_threshold,
+            groups.len()
+        )));
+    }
+    if groups
+        .iter()
+        .any(|group| group.member_threshold == 1 && group.member_count > 1)
+    {
+        return Err(BIP32Error::InvalidSharingParameters(String::from(
+            "multiple member shares with member threshold 1 are not allowed, use 1-of-1 member sharing instead",
+        )));
+    }
+
+    let identifier = (rng.next_u32() as u16) & 0x7FFF;
+    let encrypted_secret = encrypt(
+        master_secret,
+        passphrase.as_bytes(),
+        iteration_exponent,
+        identifier,
+        extendable,
+    );
+
+    let group_secrets = split_secret(group_threshold, groups.len() as u8, &encrypted_secret, rng)?;
+
+    groups
+        .iter()
+        .zip(group_secrets.iter())
+        .map(|(group, (group_index, group_secret))| {
+            Ok(
+                split_secret(group.member_threshold, group.member_count, group_secret, rng)?
+                    .iter()
+                    .map(|(member_index, value)| Share {
+                        identifier,
+                        extendable,
+                        iteration_exponent,
+                        group_index: *group_index,
+                        group_threshold,
+                        group_count: groups.len() as u8,
+                        member_index: *member_index,
+                        member_threshold: group.member_threshold,
+                        value: Bytes::new(value.to_vec()),
+                    })
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+///
+/// Decode the mnemonics and recover the master secret, see `combine_shares`
+///
+pub fn combine_mnemonics(mnemonics: &[String], passphrase: &str) -> Result<Bytes, BIP32Error> {
+    let shares = mnemonics
+        .iter()
+        .map(|mnemonic| Share::from_mnemonic(mnemonic))
+        .collect::<Result<Vec<Share>, BIP32Error>>()?;
+
+    combine_shares(&shares, passphrase)
+}
+
+///
+/// Recover the master secret from exactly `group_threshold` groups of `member_threshold` shares,
+/// then decrypt it with the passphrase.
+/// A wrong passphrase can't be detected, it gives a different master secret
+///
+pub fn combine_shares(shares: &[Share], passphrase: &str) -> Result<Bytes, BIP32Error> {
+    let first = shares
+        .first()
+        .ok_or_else(|| BIP32Error::InvalidShareSet(String::from("no share provided")))?;
+    check_passphrase(passphrase)?;
+
+    if shares.iter().any(|share| {
+        share.identifier != first.identifier
+            || share.extendable != first.extendable
+            || share.iteration_exponent != first.iteration_exponent
+            || share.group_threshold != first.group_threshold
+            || share.group_count != first.group_count
+            || share.value.nb_bytes() != first.value.nb_bytes()
+    }) {
+        return Err(BIP32Error::InvalidShareSet(String::from(
+            "the shares don't belong to the same master secret",
+        )));
+    }
+
+    let mut groups: BTreeMap<u8, Vec<&Share>> = BTreeMap::new();
+    for share in shares {
+        groups.entry(share.group_index).or_default().push(share);
+    }
+
+    if groups.len() != first.group_threshold as usize {
+        return Err(BIP32Error::InvalidShareSet(format!(
+            "{} groups are required, {} provided",
+            first.group_threshold,
+            groups.len()
+        )));
+    }
+
+    let group_secrets = groups
+        .iter()
+        .map(|(group_index, members)| {
+            let member_threshold = members[0].member_threshold;
+            if members
+                .iter()
+                .any(|member| member.member_threshold != member_threshold)
+            {
+                return Err(BIP32Error::InvalidShareSet(format!(
+                    "the shares of the group {} have different member thresholds",
+                    group_index
+                )));
+            }
+            if members.len() != member_threshold as usize {
+                return Err(BIP32Error::InvalidShareSet(format!(
+                    "{} shares are required for the group {}, {} provided",
+                    member_threshold,
+                    group_index,
+                    members.len()
+                )));
+            }
+
+            let member_shares: Vec<(u8, &[u8])> = members
+                .iter()
+                .map(|member| (member.member_index, member.value.as_ref()))
+                .collect();
+
+            Ok((
+                *group_index,
+                recover_secret(member_threshold, &member_shares)?,
+            ))
+        })
+        .collect::<Result<Vec<SecretShare>, BIP32Error>>()?;
+
+    let group_shares: Vec<(u8, &[u8])> = group_secrets
+        .iter()
+        .map(|(group_index, secret)| (*group_index, secret.as_slice()))
+        .collect();
+    let encrypted_secret = recover_secret(first.group_threshold, &group_shares)?;
+
+    Ok(Bytes::new(
+        decrypt(
+            &encrypted_secret,
+            passphrase.as_bytes(),
+            first.iteration_exponent,
+            first.identifier,
+            first.extendable,
+        )
+        .to_vec(),
+    ))
+}
+
+///
+/// Recover the master secret as entropy, to build the BIP39 mnemonic
+///
+pub fn combine_mnemonics_to_entropy(
+    mnemonics: &[String],
+    passphrase: &str,
+) -> Result<Entropy, BIP32Error> {
+    Entropy::from_bytes_vec(combine_mnemonics(mnemonics, passphrase)?.into_vec())
+}
+
+///
+/// The passphrase must only contain printable ASCII characters
+///
+fn check_passphrase(passphrase: &str) -> Result<(), BIP32Error> {
+    if passphrase.bytes().all(|c| (32..=126).contains(&c)) {
+        Ok(())
+    } else {
+        Err(BIP32Error::InvalidPassphrase)
+    }
+}
+
+fn word_index(word: &str) -> Result<u16, BIP32Error> {
+    words_list::SLIP39
+        .binary_search(&word)
+        .map(|index| index as u16)
+        .map_err(|_| BIP32Error::UnknownWord(word.to_owned()))
+}
+
+fn customization_string(extendable: bool) -> &'static [u8] {
+    if extendable {
+        CUSTOMIZATION_STRING_EXTENDABLE
+    } else {
+        CUSTOMIZATION_STRING
+    }
+}
+
+///
+/// Reed-Solomon code over GF(1024), the checksum detects up to 3 wrong words
+///
+fn rs1024_polymod(values: impl Iterator<Item = u32>) -> u32 {
+    values.fold(1, |checksum, value| {
+        let top = checksum >> 20;
+        let checksum = ((checksum & 0xFFFFF) << RADIX_BITS) ^ value;
+        RS1024_GENERATOR
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| (top >> i) & 1 == 1)
+            .fold(checksum, |checksum, (_, generator)| checksum ^ generator)
+    })
+}
+
+fn rs1024_create_checksum(customization: &[u8], data: &[u16]) -> [u16; CHECKSUM_WORDS] {
+    let values = customization
+        .iter()
+        .map(|c| *c as u32)
+        .chain(data.iter().map(|index| *index as u32))
+        .chain([0; CHECKSUM_WORDS]);
+    let polymod = rs1024_polymod(values) ^ 1;
+
+    [
+        ((polymod >> 20) & RADIX_MASK) as u16,
+        ((polymod >> 10) & RADIX_MASK) as u16,
+        (polymod & RADIX_MASK) as u16,
+    ]
+}
+
+fn rs1024_verify_checksum(customization: &[u8], data: &[u16]) -> bool {
+    let values = customization
+        .iter()
+        .map(|c| *c as u32)
+        .chain(data.iter().map(|index| *index as u32));
+
+    rs1024_polymod(values) == 1
+}
+
+///
+/// Convert the bytes to 10 bits words, left padded with zeros
+///
+fn bytes_to_words(bytes: &[u8]) -> Zeroizing<Vec<u16>> {
+    let nb_bits = bytes.len() * NB_BITS_IN_BYTE;
+    let nb_words = nb_bits.div_ceil(RADIX_BITS);
+
+    let mut words = Zeroizing::new(Vec::with_capacity(nb_words));
+    let mut buffer: u32 = 0;
+    let mut buffer_bits = nb_words * RADIX_BITS - nb_bits;
+    for byte in bytes {
+        buffer = (buffer << NB_BITS_IN_BYTE) | *byte as u32;
+        buffer_bits += NB_BITS_IN_BYTE;
+        if buffer_bits >= RADIX_BITS {
+            buffer_bits -= RADIX_BITS;
+            words.push(((buffer >> buffer_bits) & RADIX_MASK) as u16);
+            buffer &= (1 << buffer_bits) - 1;
+        }
+    }
+    buffer.zeroize();
+
+    words
+}
+
+///
+/// Convert the 10 bits words to bytes, the padding must be at most 8 bits of zeros
+///
+fn words_to_bytes(words: &[u16]) -> Result<Zeroizing<Vec<u8>>, BIP32Error> {
+    let padding = (words.len() * RADIX_BITS) % 16;
+    if padding > 8 {
+        return Err(BIP32Error::InvalidShare(String::from(
+            "invalid padding length",
+        )));
+    }
+    if words
+        .first()
+        .is_some_and(|word| *word >> (RADIX_BITS - padding) != 0)
+    {
+        return Err(BIP32Error::InvalidShare(String::from("invalid padding")));
+    }
+
+    let mut bytes = Zeroizing::new(Vec::with_capacity(
+        words.len() * RADIX_BITS / NB_BITS_IN_BYTE,
+    ));
+    let mut buffer: u32 = 0;
+    let mut buffer_bits = 0;
+    for (i, word) in words.iter().enumerate() {
+        buffer = (buffer << RADIX_BITS) | *word as u32;
+        buffer_bits += RADIX_BITS;
+        if i == 0 {
+            buffer_bits -= padding;
+        }
+        while buffer_bits >= NB_BITS_IN_BYTE {
+            buffer_bits -= NB_BITS_IN_BYTE;
+            bytes.push((buffer >> buffer_bits) as u8);
+            buffer &= (1 << buffer_bits) - 1;
+        }
+    }
+    buffer.zeroize();
+
+    Ok(bytes)
+}
+
+///
+/// Split the secret into `share_count` shares, any `threshold` of them can recover the secret.
+/// The polynomial goes through the secret (x = 255) and a digest of the secret (x = 254),
+/// so a wrong combination of shares is detected when recovering
+///
+fn split_secret(
+    threshold: u8,
+    share_count: u8,
+    secret: &[u8],
+    rng: &mut dyn CryptoRngCore,
+) -> Result<Vec<SecretShare>, BIP32Error> {
+    if threshold == 0 || threshold > share_count || share_count > MAX_SHARE_COUNT {
+        return Err(BIP32Error::InvalidSharingParameters(format!(
+            "the threshold ({}) must be between 1 and the shares count ({}), which is at most {}",
+            threshold, share_count, MAX_SHARE_COUNT
+        )));
+    }
+
+    if threshold == 1 {
+        return Ok((0..share_count)
+            .map(|index| (index, Zeroizing::new(secret.to_vec())))
+            .collect());
+    }
+
+    let random_share_count = threshold - 2;
+    let mut shares: Vec<SecretShare> = (0..random_share_count)
+        .map(|index| (index, random_bytes(secret.len(), rng)))
+        .collect();
+
+    let random_part = random_bytes(secret.len() - DIGEST_LEN, rng);
+    let mut digest = Zeroizing::new(create_digest(&random_part, secret)?.to_vec());
+    digest.extend_from_slice(&random_part);
+
+    let mut base_shares: Vec<(u8, &[u8])> = shares
+        .iter()
+        .map(|(index, value)| (*index, value.as_slice()))
+        .collect();
+    base_shares.push((DIGEST_INDEX, &digest));
+    base_shares.push((SECRET_INDEX, secret));
+
+    let derived_shares = (random_share_count..share_count)
+        .map(|index| Ok((index, interpolate(&base_shares, index)?)))
+        .collect::<Result<Vec<SecretShare>, BIP32Error>>()?;
+    shares.extend(derived_shares);
+
+    Ok(shares)
+}
+
+///
+/// Recover the secret from `threshold` shares and check its digest
+///
+fn recover_secret(threshold: u8, shares: &[(u8, &[u8])]) -> Result<Zeroizing<Vec<u8>>, BIP32Error> {
+    if threshold == 1 {
+        return Ok(Zeroizing::new(shares[0].1.to_vec()));
+    }
+
+    let secret = interpolate(shares, SECRET_INDEX)?;
+    let digest_share = interpolate(shares, DIGEST_INDEX)?;
+
+    if create_digest(&digest_share[DIGEST_LEN..], &secret)?[..] != digest_share[..DIGEST_LEN] {
+        return Err(BIP32Error::InvalidShareDigest);
+    }
+
+    Ok(secret)
+}
+
+///
+/// First 4 bytes of HMAC-SHA256(Key = random part, Data = secret)
+///
+fn create_digest(random_part: &[u8], secret: &[u8]) -> Result<[u8; DIGEST_LEN], BIP32Error> {
+    let mut hmac = Hmac::<Sha256>::new_from_slice(random_part)
+        .map_err(|e| BIP32Error::HmacError(e.to_string()))?;
+    hmac.update(secret);
+    let result = hmac.finalize().into_bytes();
+
+    Ok([result[0], result[1], result[2], result[3]])
+}
+
+///
+/// Lagrange interpolation of the polynomial going through the shares, evaluated in x
+///
+fn interpolate(shares: &[(u8, &[u8])], x: u8) -> Result<Zeroizing<Vec<u8>>, BIP32Error> {
+    let (exp, log) = &GF256;
+
+    if shares
+        .iter()
+        .enumerate()
+        .any(|(i, (index, _))| shares[..i].iter().any(|(other, _)| other == index))
+    {
+        return Err(BIP32Error::InvalidShareSet(String::from(
+            "the share indexes must be unique",
+        )));
+    }
+
+    let len = shares[0].1.len();
+    if shares.iter().any(|(_, value)| value.len() != len) {
+        return Err(BIP32Error::InvalidShareSet(String::from(
+            "the share values must have the same length",
+        )));
+    }
+
+    if let Some((_, value)) = shares.iter().find(|(index, _)| *index == x) {
+        return Ok(Zeroizing::new(value.to_vec()));
+    }
+
+    let log_product: isize = shares
+        .iter()
+        .map(|(index, _)| log[(index ^ x) as usize] as isize)
+        .sum();
+
+    let mut result = Zeroizing::new(vec![0u8; len]);
+    for (index, value) in shares {
+        let log_basis = (log_product
+            - log[(index ^ x) as usize] as isize
+            - shares
+                .iter()
+                .map(|(other, _)| log[(index ^ other) as usize] as isize)
+                .sum::<isize>())
+        .rem_euclid(255) as usize;
+
+        for (result_byte, value_byte) in result.iter_mut().zip(value.iter()) {
+            if *value_byte != 0 {
+                *result_byte ^= exp[(log[*value_byte as usize] as usize + log_basis) % 255];
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+fn random_bytes(len: usize, rng: &mut dyn CryptoRngCore) -> Zeroizing<Vec<u8>> {
+    let mut bytes = Zeroizing::new(vec![0u8; len]);
+    rng.fill_bytes(&mut bytes);
+    bytes
+}
+
+///
+/// Encrypt the master secret with a 4 rounds Feistel network, the round function is PBKDF2
+///
+fn encrypt(
+    master_secret: &[u8],
+    passphrase: &[u8],
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+) -> Zeroizing<Vec<u8>> {
+    feistel(
+        master_secret,
+        passphrase,
+        iteration_exponent,
+        identifier,
+        extendable,
+        0..ROUND_COUNT,
+    )
+}
+
+///
+/// Decrypt the encrypted master secret, the rounds are applied in the reverse order
+///
+fn decrypt(
+    encrypted_secret: &[u8],
+    passphrase: &[u8],
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+) -> Zeroizing<Vec<u8>> {
+    feistel(
+        encrypted_secret,
+        passphrase,
+        iteration_exponent,
+        identifier,
+        extendable,
+        (0..ROUND_COUNT).rev(),
+    )
+}
+
+fn feistel(
+    secret: &[u8],
+    passphrase: &[u8],
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+    rounds: impl Iterator<Item = u8>,
+) -> Zeroizing<Vec<u8>> {
+    // The identifier is part of the salt of non extendable shares only
+    let mut salt = Vec::new();
+    if !extendable {
+        salt.extend_from_slice(CUSTOMIZATION_STRING);
+        salt.extend_from_slice(&identifier.to_be_bytes());
+    }
+    let iterations = (BASE_ITERATION_COUNT << iteration_exponent) / ROUND_COUNT as u32;
+
+    let half = secret.len() / 2;
+    let mut left = Zeroizing::new(secret[..half].to_vec());
+    let mut right = Zeroizing::new(secret[half..].to_vec());
+
+    for round in rounds {
+        let mut password = Zeroizing::new(vec![round]);
+        password.extend_from_slice(passphrase);
+        let mut round_salt = Zeroizing::new(salt.clone());
+        round_salt.extend_from_slice(&right);
+
+        let mut key = Zeroizing::new(vec![0u8; right.len()]);
+        pbkdf2::pbkdf2::<Hmac<Sha256>>(&password, &round_salt, iterations, &mut key);
+
+        for (left_byte, key_byte) in left.iter_mut().zip(key.iter()) {
+            *left_byte ^= key_byte;
+        }
+        std::mem::swap(&mut left, &mut right);
+    }
+
+    right.extend_from_slice(&left);
+    right
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bytes_to_words, interpolate, split_secret, words_to_bytes, GF256};
+
+    ///
+    /// Words conversion round trip, with the left padding
+    ///
+    #[test]
+    fn bytes_to_words_round_trip() {
+        for len in [16usize, 18, 20, 32] {
+            let bytes: Vec<u8> = (0..len).map(|i| (i * 37 + 255 - i) as u8).collect();
+            let words = bytes_to_words(&bytes);
+
+            assert_eq!(words.len(), (len * 8).div_ceil(10));
+            assert!(words.iter().all(|word| *word < 1024));
+            assert_eq!(words_to_bytes(&words).unwrap().to_vec(), bytes);
+        }
+    }
+
+    ///
+    /// Every non zero element has an inverse in GF(256)
+    ///
+    #[test]
+    fn gf256_tables() {
+        let (exp, log) = &GF256;
+
+        assert_eq!(exp[0], 1);
+        assert_eq!(exp[1], 3);
+        for value in 1..=255u8 {
+            assert_eq!(exp[log[value as usize] as usize], value);
+        }
+    }
+
+    ///
+    /// Any threshold shares recover the secret
+    ///
+    #[test]
+    fn split_and_interpolate_secret() {
+        let secret = b"0123456789abcdef";
+        let shares = split_secret(3, 5, secret, &mut rand::rngs::OsRng).unwrap();
+
+        for combination in [[0, 1, 2], [0, 2, 4], [4, 3, 1]] {
+            let subset: Vec<(u8, &[u8])> = combination
+                .iter()
+                .map(|i| (shares[*i].0, shares[*i].1.as_slice()))
+                .collect();
+            assert_eq!(interpolate(&subset, 255).unwrap().as_slice(), secret);
+        }
+    }
+}
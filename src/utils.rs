@@ -2,11 +2,16 @@ use hmac::Hmac;
 use pbkdf2;
 use sha2::{Digest, Sha256};
 use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroize;
+
+/* Config */
+// Combining diacritical marks block, the accents of the Latin letters after NFKD
+const LATIN_DIACRITICS: std::ops::RangeInclusive<char> = '\u{0300}'..='\u{036F}';
 
 ///
 /// Perform the SHA256 hash function
 ///
-pub fn sha256(bytes: &Vec<u8>) -> Vec<u8> {
+pub fn sha256(bytes: &[u8]) -> Vec<u8> {
     let mut hasher = Sha256::new();
     hasher.update(bytes);
     hasher.finalize().to_vec()
@@ -17,19 +22,86 @@ pub fn sha256(bytes: &Vec<u8>) -> Vec<u8> {
 ///     To create a binary seed from the mnemonic, we use the PBKDF2 function with a mnemonic sentence (in UTF-8 NFKD) used as the password and
 ///     the string "mnemonic" + passphrase (again in UTF-8 NFKD) used as the salt. The iteration count is set to 2048 and HMAC-SHA512 is used as the pseudo-random function.
 ///     The length of the derived key is 512 bits (= 64 bytes).
+/// The password and the salt are wiped once the key is derived
 ///
-pub fn pbkdf2_hash(password: Vec<u8>, salt: Vec<u8>) -> Vec<u8> {
+pub fn pbkdf2_hash(mut password: Vec<u8>, mut salt: Vec<u8>) -> Vec<u8> {
     let mut seed = vec![0u8; 64];
     pbkdf2::pbkdf2::<Hmac<sha2::Sha512>>(&password, &salt, 2048, &mut seed);
 
+    password.zeroize();
+    salt.zeroize();
     seed
 }
 
 ///
-/// Normalize word to NFKD
+/// Normalize word to NFKD, the given word is wiped as it can be a mnemonic or a passphrase
 /// 
-pub fn to_utf8_nfkd(word: String) -> String {
-    word.nfkd().collect::<String>()
+pub fn to_utf8_nfkd(mut word: String) -> String {
+    let normalized = word.nfkd().collect::<String>();
+    word.zeroize();
+
+    normalized
+}
+
+///
+/// Lowercase the word and remove its diacritics ("Étude" => "etude") to compare words typed without accents.
+/// Only the Latin diacritics are removed, the japanese (semi-)voiced sound marks change the kana ("か" / "が")
+///
+pub fn fold_word(word: &str) -> String {
+    word.nfkd()
+        .filter(|c| !LATIN_DIACRITICS.contains(c))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+///
+/// Damerau-Levenshtein (optimal string alignment) distance, each insertion, deletion, substitution or
+/// transposition of two adjacent characters costs 1
+///
+pub fn damerau_levenshtein(a: &str, b: &str) -> usize {
+    weighted_damerau_levenshtein(a, b, 1, 1, |_, _| 1)
+}
+
+///
+/// Damerau-Levenshtein (optimal string alignment) distance with the cost of the insertion / deletion,
+/// of the transposition and of the substitution of two different characters
+///
+pub(crate) fn weighted_damerau_levenshtein(
+    a: &str,
+    b: &str,
+    edit_cost: usize,
+    transposition_cost: usize,
+    substitution_cost: impl Fn(char, char) -> usize,
+) -> usize {
+    let a = a.chars().collect::<Vec<char>>();
+    let b = b.chars().collect::<Vec<char>>();
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i * edit_cost;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j * edit_cost;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = match a[i - 1] == b[j - 1] {
+                true => 0,
+                false => substitution_cost(a[i - 1], b[j - 1]),
+            };
+
+            let mut distance = (distances[i - 1][j] + edit_cost)
+                .min(distances[i][j - 1] + edit_cost)
+                .min(distances[i - 1][j - 1] + substitution);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + transposition_cost);
+            }
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[a.len()][b.len()]
 }
 
 #[cfg(test)]
@@ -62,13 +134,47 @@ mod tests {
         for input in inputs {
             assert_eq!(
                 input.1,
-                hex::encode(utils::sha256(&input.0.as_bytes().to_vec()))
+                hex::encode(utils::sha256(input.0.as_bytes()))
             )
         }
 
         assert_eq!(
-            hex::encode(utils::sha256(&Entropy::default().entropy.as_vec())),
+            hex::encode(utils::sha256(Entropy::default().entropy.as_vec())),
             String::from("66687aadf862bd776c8fc18b8e9f8e20089714856ee233b3902a591d0d5f2925")
         );
     }
+
+    ///
+    /// Diacritics and case are removed
+    ///
+    #[test]
+    fn test_fold_word() {
+        assert_eq!(utils::fold_word("Étude"), "etude");
+        assert_eq!(utils::fold_word(&utils::to_utf8_nfkd("ábaco".to_owned())), "abaco");
+        assert_eq!(utils::fold_word("abandon"), "abandon");
+
+        // The japanese voiced sound mark is kept
+        let ga = utils::to_utf8_nfkd("が".to_owned());
+        assert_eq!(utils::fold_word(&ga), ga);
+        assert_ne!(utils::fold_word(&ga), utils::fold_word("か"));
+    }
+
+    ///
+    /// Each edit costs 1, swapped adjacent letters count as one edit
+    ///
+    #[test]
+    fn test_damerau_levenshtein() {
+        let inputs = vec![
+            ("abandon", "abandon", 0),
+            ("abandn", "abandon", 1),
+            ("abnadon", "abandon", 1),
+            ("abamdon", "abandon", 1),
+            ("ca", "abc", 3),
+            ("", "abc", 3),
+        ];
+
+        for (a, b, distance) in inputs {
+            assert_eq!(utils::damerau_levenshtein(a, b), distance);
+        }
+    }
 }
@@ -0,0 +1,281 @@
+use std::str::FromStr;
+
+use bip39::bip32::{ChildNumber, DerivationPath, ExtendedPrivateKey, ExtendedPublicKey};
+use bip39::BIP32Error;
+
+#[test]
+fn derive_from_test_vector_1() {
+    // Vector from https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#test-vector-1
+    let seed = "000102030405060708090a0b0c0d0e0f";
+    let list = vec![
+        (
+            "m",
+            "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+            "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8"
+        ),
+        (
+            "m/0'",
+            "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7",
+            "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw"
+        ),
+        (
+            "m/0'/1",
+            "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs",
+            "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ"
+        ),
+        (
+            "m/0'/1/2'",
+            "xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM",
+            "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5"
+        ),
+        (
+            "m/0'/1/2'/2",
+            "xprvA2JDeKCSNNZky6uBCviVfJSKyQ1mDYahRjijr5idH2WwLsEd4Hsb2Tyh8RfQMuPh7f7RtyzTtdrbdqqsunu5Mm3wDvUAKRHSC34sJ7in334",
+            "xpub6FHa3pjLCk84BayeJxFW2SP4XRrFd1JYnxeLeU8EqN3vDfZmbqBqaGJAyiLjTAwm6ZLRQUMv1ZACTj37sR62cfN7fe5JnJ7dh8zL4fiyLHV"
+        ),
+        (
+            "m/0'/1/2'/2/1000000000",
+            "xprvA41z7zogVVwxVSgdKUHDy1SKmdb533PjDz7J6N6mV6uS3ze1ai8FHa8kmHScGpWmj4WggLyQjgPie1rFSruoUihUZREPSL39UNdE3BBDu76",
+            "xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYFgJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy"
+        ),
+    ];
+
+    derive_from_test_vector(seed, list);
+}
+
+#[test]
+fn derive_from_test_vector_2() {
+    // Vector from https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#test-vector-2
+    let seed = "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542";
+    let list = vec![
+        (
+            "m",
+            "xprv9s21ZrQH143K31xYSDQpPDxsXRTUcvj2iNHm5NUtrGiGG5e2DtALGdso3pGz6ssrdK4PFmM8NSpSBHNqPqm55Qn3LqFtT2emdEXVYsCzC2U",
+            "xpub661MyMwAqRbcFW31YEwpkMuc5THy2PSt5bDMsktWQcFF8syAmRUapSCGu8ED9W6oDMSgv6Zz8idoc4a6mr8BDzTJY47LJhkJ8UB7WEGuduB"
+        ),
+        (
+            "m/0",
+            "xprv9vHkqa6EV4sPZHYqZznhT2NPtPCjKuDKGY38FBWLvgaDx45zo9WQRUT3dKYnjwih2yJD9mkrocEZXo1ex8G81dwSM1fwqWpWkeS3v86pgKt",
+            "xpub69H7F5d8KSRgmmdJg2KhpAK8SR3DjMwAdkxj3ZuxV27CprR9LgpeyGmXUbC6wb7ERfvrnKZjXoUmmDznezpbZb7ap6r1D3tgFxHmwMkQTPH"
+        ),
+        (
+            "m/0/2147483647'",
+            "xprv9wSp6B7kry3Vj9m1zSnLvN3xH8RdsPP1Mh7fAaR7aRLcQMKTR2vidYEeEg2mUCTAwCd6vnxVrcjfy2kRgVsFawNzmjuHc2YmYRmagcEPdU9",
+            "xpub6ASAVgeehLbnwdqV6UKMHVzgqAG8Gr6riv3Fxxpj8ksbH9ebxaEyBLZ85ySDhKiLDBrQSARLq1uNRts8RuJiHjaDMBU4Zn9h8LZNnBC5y4a"
+        ),
+        (
+            "m/0/2147483647'/1",
+            "xprv9zFnWC6h2cLgpmSA46vutJzBcfJ8yaJGg8cX1e5StJh45BBciYTRXSd25UEPVuesF9yog62tGAQtHjXajPPdbRCHuWS6T8XA2ECKADdw4Ef",
+            "xpub6DF8uhdarytz3FWdA8TvFSvvAh8dP3283MY7p2V4SeE2wyWmG5mg5EwVvmdMVCQcoNJxGoWaU9DCWh89LojfZ537wTfunKau47EL2dhHKon"
+        ),
+        (
+            "m/0/2147483647'/1/2147483646'",
+            "xprvA1RpRA33e1JQ7ifknakTFpgNXPmW2YvmhqLQYMmrj4xJXXWYpDPS3xz7iAxn8L39njGVyuoseXzU6rcxFLJ8HFsTjSyQbLYnMpCqE2VbFWc",
+            "xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL"
+        ),
+        (
+            "m/0/2147483647'/1/2147483646'/2",
+            "xprvA2nrNbFZABcdryreWet9Ea4LvTJcGsqrMzxHx98MMrotbir7yrKCEXw7nadnHM8Dq38EGfSh6dqA9QWTyefMLEcBYJUuekgW4BYPJcr9E7j",
+            "xpub6FnCn6nSzZAw5Tw7cgR9bi15UV96gLZhjDstkXXxvCLsUXBGXPdSnLFbdpq8p9HmGsApME5hQTZ3emM2rnY5agb9rXpVGyy3bdW6EEgAtqt"
+        ),
+    ];
+
+    derive_from_test_vector(seed, list);
+}
+
+#[test]
+fn derive_from_test_vector_3() {
+    // Vector from https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#test-vector-3
+    let seed = "4b381541583be4423346c643850da4b320e46a87ae3d2a4e6da11eba819cd4acba45d239319ac14f863b8d5ab5a0d0c64d2e8a1e7d1457df2e5a3c51c73235be";
+    let list = vec![
+        (
+            "m",
+            "xprv9s21ZrQH143K25QhxbucbDDuQ4naNntJRi4KUfWT7xo4EKsHt2QJDu7KXp1A3u7Bi1j8ph3EGsZ9Xvz9dGuVrtHHs7pXeTzjuxBrCmmhgC6",
+            "xpub661MyMwAqRbcEZVB4dScxMAdx6d4nFc9nvyvH3v4gJL378CSRZiYmhRoP7mBy6gSPSCYk6SzXPTf3ND1cZAceL7SfJ1Z3GC8vBgp2epUt13"
+        ),
+        (
+            "m/0'",
+            "xprv9uPDJpEQgRQfDcW7BkF7eTya6RPxXeJCqCJGHuCJ4GiRVLzkTXBAJMu2qaMWPrS7AANYqdq6vcBcBUdJCVVFceUvJFjaPdGZ2y9WACViL4L",
+            "xpub68NZiKmJWnxxS6aaHmn81bvJeTESw724CRDs6HbuccFQN9Ku14VQrADWgqbhhTHBaohPX4CjNLf9fq9MYo6oDaPPLPxSb7gwQN3ih19Zm4Y"
+        ),
+    ];
+
+    derive_from_test_vector(seed, list);
+}
+
+#[test]
+fn derive_from_test_vector_4() {
+    // Vector from https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#test-vector-4
+    let seed = "3ddd5602285899a946114506157c7997e5444528f3003f6134712147db19b678";
+    let list = vec![
+        (
+            "m",
+            "xprv9s21ZrQH143K48vGoLGRPxgo2JNkJ3J3fqkirQC2zVdk5Dgd5w14S7fRDyHH4dWNHUgkvsvNDCkvAwcSHNAQwhwgNMgZhLtQC63zxwhQmRv",
+            "xpub661MyMwAqRbcGczjuMoRm6dXaLDEhW1u34gKenbeYqAix21mdUKJyuyu5F1rzYGVxyL6tmgBUAEPrEz92mBXjByMRiJdba9wpnN37RLLAXa"
+        ),
+        (
+            "m/0'",
+            "xprv9vB7xEWwNp9kh1wQRfCCQMnZUEG21LpbR9NPCNN1dwhiZkjjeGRnaALmPXCX7SgjFTiCTT6bXes17boXtjq3xLpcDjzEuGLQBM5ohqkao9G",
+            "xpub69AUMk3qDBi3uW1sXgjCmVjJ2G6WQoYSnNHyzkmdCHEhSZ4tBok37xfFEqHd2AddP56Tqp4o56AePAgCjYdvpW2PU2jbUPFKsav5ut6Ch1m"
+        ),
+        (
+            "m/0'/1'",
+            "xprv9xJocDuwtYCMNAo3Zw76WENQeAS6WGXQ55RCy7tDJ8oALr4FWkuVoHJeHVAcAqiZLE7Je3vZJHxspZdFHfnBEjHqU5hG1Jaj32dVoS6XLT1",
+            "xpub6BJA1jSqiukeaesWfxe6sNK9CCGaujFFSJLomWHprUL9DePQ4JDkM5d88n49sMGJxrhpjazuXYWdMf17C9T5XnxkopaeS7jGk1GyyVziaMt"
+        ),
+    ];
+
+    derive_from_test_vector(seed, list);
+}
+
+#[test]
+fn derive_public_child_from_extended_public_key() {
+    // Non hardened derivation from the xpub should match the xpub of the derived xprv
+    let list = vec![
+        ("000102030405060708090a0b0c0d0e0f", "m/0'/1/2'/2", "m/1000000000"),
+        ("fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542", "m/0", "m/2147483647/1"),
+    ];
+
+    for (seed, parent_path, child_path) in list.into_iter() {
+        let master = ExtendedPrivateKey::new_master(&hex::decode(seed).unwrap()).unwrap();
+        let parent = master.derive_path(&DerivationPath::from_str(parent_path).unwrap()).unwrap();
+        let child = parent.derive_path(&DerivationPath::from_str(child_path).unwrap()).unwrap();
+
+        let public_child = parent
+            .to_extended_public_key()
+            .derive_path(&DerivationPath::from_str(child_path).unwrap())
+            .unwrap();
+        assert_eq!(public_child, child.to_extended_public_key());
+    }
+
+    // Hardened child can't be derived from the public key
+    let master = ExtendedPrivateKey::new_master(&hex::decode("000102030405060708090a0b0c0d0e0f").unwrap()).unwrap();
+    assert_eq!(
+        master.to_extended_public_key().derive_child(ChildNumber::Hardened(0)),
+        Err(BIP32Error::HardenedDerivationFromPublicKey)
+    );
+}
+
+#[test]
+fn parse_invalid_extended_keys_from_test_vector_5() {
+    // Vector from https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#test-vector-5
+    // (Description, Extended public key, Error message)
+    let invalid_xpubs = vec![
+        (
+            "pubkey version / prvkey mismatch",
+            "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6LBpB85b3D2yc8sfvZU521AAwdZafEz7mnzBBsz4wKY5fTtTQBm",
+            "invalid public key prefix",
+        ),
+        (
+            "invalid pubkey prefix 04",
+            "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6Txnt3siSujt9RCVYsx4qHZGc62TG4McvMGcAUjeuwZdduYEvFn",
+            "invalid public key prefix",
+        ),
+        (
+            "invalid pubkey prefix 01",
+            "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6N8ZMMXctdiCjxTNq964yKkwrkBJJwpzZS4HS2fxvyYUA4q2Xe4",
+            "invalid public key prefix",
+        ),
+        (
+            "zero depth with non-zero parent fingerprint",
+            "xpub661no6RGEX3uJkY4bNnPcw4URcQTrSibUZ4NqJEw5eBkv7ovTwgiT91XX27VbEXGENhYRCf7hyEbWrR3FewATdCEebj6znwMfQkhRYHRLpJ",
+            "zero depth with non-zero parent fingerprint or index",
+        ),
+        (
+            "zero depth with non-zero index",
+            "xpub661MyMwAuDcm6CRQ5N4qiHKrJ39Xe1R1NyfouMKTTWcguwVcfrZJaNvhpebzGerh7gucBvzEQWRugZDuDXjNDRmXzSZe4c7mnTK97pTvGS8",
+            "zero depth with non-zero parent fingerprint or index",
+        ),
+        (
+            "unknown extended key version",
+            "DMwo58pR1QLEFihHiXPVykYB6fJmsTeHvyTp7hRThAtCX8CvYzgPcn8XnmdfHPmHJiEDXkTiJTVV9rHEBUem2mwVbbNfvT2MTcAqj3nesx8uBf9",
+            "invalid version",
+        ),
+        (
+            "invalid pubkey 020000000000000000000000000000000000000000000000000000000000000007",
+            "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6Q5JXayek4PRsn35jii4veMimro1xefsM58PgBMrvdYre8QyULY",
+            "invalid public key",
+        ),
+    ];
+    for (description, xpub, message) in invalid_xpubs.into_iter() {
+        assert_eq!(
+            ExtendedPublicKey::from_str(xpub),
+            Err(BIP32Error::InvalidExtendedKey(message.to_owned())),
+            "{}",
+            description
+        );
+    }
+
+    // (Description, Extended private key, Error message)
+    let invalid_xprvs = vec![
+        (
+            "prvkey version / pubkey mismatch",
+            "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFGTQQD3dC4H2D5GBj7vWvSQaaBv5cxi9gafk7NF3pnBju6dwKvH",
+            "invalid private key prefix",
+        ),
+        (
+            "invalid prvkey prefix 04",
+            "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFGpWnsj83BHtEy5Zt8CcDr1UiRXuWCmTQLxEK9vbz5gPstX92JQ",
+            "invalid private key prefix",
+        ),
+        (
+            "invalid prvkey prefix 01",
+            "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFAzHGBP2UuGCqWLTAPLcMtD9y5gkZ6Eq3Rjuahrv17fEQ3Qen6J",
+            "invalid private key prefix",
+        ),
+        (
+            "zero depth with non-zero parent fingerprint",
+            "xprv9s2SPatNQ9Vc6GTbVMFPFo7jsaZySyzk7L8n2uqKXJen3KUmvQNTuLh3fhZMBoG3G4ZW1N2kZuHEPY53qmbZzCHshoQnNf4GvELZfqTUrcv",
+            "zero depth with non-zero parent fingerprint or index",
+        ),
+        (
+            "zero depth with non-zero index",
+            "xprv9s21ZrQH4r4TsiLvyLXqM9P7k1K3EYhA1kkD6xuquB5i39AU8KF42acDyL3qsDbU9NmZn6MsGSUYZEsuoePmjzsB3eFKSUEh3Gu1N3cqVUN",
+            "zero depth with non-zero parent fingerprint or index",
+        ),
+        (
+            "unknown extended key version",
+            "DMwo58pR1QLEFihHiXPVykYB6fJmsTeHvyTp7hRThAtCX8CvYzgPcn8XnmdfHGMQzT7ayAmfo4z3gY5KfbrZWZ6St24UVf2Qgo6oujFktLHdHY4",
+            "invalid version",
+        ),
+        (
+            "private key 0 not in 1..n-1",
+            "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzF93Y5wvzdUayhgkkFoicQZcP3y52uPPxFnfoLZB21Teqt1VvEHx",
+            "invalid private key",
+        ),
+        (
+            "private key n not in 1..n-1",
+            "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFAzHGBP2UuGCqWLTAPLcMtD5SDKr24z3aiUvKr9bJpdrcLg1y3G",
+            "invalid private key",
+        ),
+    ];
+    for (description, xprv, message) in invalid_xprvs.into_iter() {
+        assert_eq!(
+            ExtendedPrivateKey::from_str(xprv),
+            Err(BIP32Error::InvalidExtendedKey(message.to_owned())),
+            "{}",
+            description
+        );
+    }
+
+    // Invalid checksum
+    assert_eq!(
+        ExtendedPrivateKey::from_str("xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHL"),
+        Err(BIP32Error::InvalidExtendedKeyChecksum)
+    );
+}
+
+fn derive_from_test_vector(seed: &str, list: Vec<(&str, &str, &str)>) {
+    let master = ExtendedPrivateKey::new_master(&hex::decode(seed).unwrap()).unwrap();
+
+    for (path, xprv, xpub) in list.into_iter() {
+        let key = master.derive_path(&DerivationPath::from_str(path).unwrap()).unwrap();
+
+        assert_eq!(xprv, key.to_string());
+        assert_eq!(xpub, key.to_extended_public_key().to_string());
+
+        // Serialized keys should be parsed back
+        assert_eq!(ExtendedPrivateKey::from_str(xprv).unwrap(), key);
+        assert_eq!(ExtendedPublicKey::from_str(xpub).unwrap(), key.to_extended_public_key());
+    }
+}
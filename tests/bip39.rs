@@ -1,7 +1,9 @@
 use bip39::entropy::{Entropy};
-use bip39::{generate_mnemonic, WordsCount, utils};
+use bip39::{generate_mnemonic, BIP32Error, get_seed_from_phrase, utils, ExtendedPrivateKey, WordsCount};
 use bip39::language::Language;
 use bip39::mnemonic::{Mnemonic, Seed};
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+use unicode_normalization::UnicodeNormalization;
 
 #[test]
 fn create_mnemonic_from_all_language() {
@@ -13,11 +15,13 @@ fn create_mnemonic_from_all_language() {
                 (Language::English, "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"),
                 (Language::French, "abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abeille"),
                 (Language::Italian, "abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco abaco abete"),
-                (Language::Japanese, "あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あいこくしん あおぞら"),
+                (Language::Japanese, "あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あおぞら"),
                 (Language::Korean, "가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 가격 가능"),
                 (Language::Portugese, "abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abacate abater"),
                 (Language::Spanish, "ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco ábaco abierto"),
                 (Language::Czech, "abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace abdikace agrese"),
+                (Language::ChineseSimplified, "的 的 的 的 的 的 的 的 的 的 的 在"),
+                (Language::ChineseTraditional, "的 的 的 的 的 的 的 的 的 的 的 在"),
             ],
         )
     ];
@@ -38,13 +42,27 @@ fn create_mnemonic_from_all_language() {
 
 #[test]
 fn generate_new_mnemonic() {
-    assert_eq!(generate_mnemonic(bip39::language::WordsCount::Words24, Language::English).unwrap().get_words().len(), 24);
-    assert_eq!(generate_mnemonic(bip39::language::WordsCount::Words21, Language::English).unwrap().get_words().len(), 21);
-    assert_eq!(generate_mnemonic(bip39::language::WordsCount::Words18, Language::English).unwrap().get_words().len(), 18);
-    assert_eq!(generate_mnemonic(bip39::language::WordsCount::Words15, Language::English).unwrap().get_words().len(), 15);
-    assert_eq!(generate_mnemonic(bip39::language::WordsCount::Words12, Language::English).unwrap().get_words().len(), 12);
+    assert_eq!(generate_mnemonic(bip39::language::WordsCount::Words24, Language::English, None).unwrap().get_words().len(), 24);
+    assert_eq!(generate_mnemonic(bip39::language::WordsCount::Words21, Language::English, None).unwrap().get_words().len(), 21);
+    assert_eq!(generate_mnemonic(bip39::language::WordsCount::Words18, Language::English, None).unwrap().get_words().len(), 18);
+    assert_eq!(generate_mnemonic(bip39::language::WordsCount::Words15, Language::English, None).unwrap().get_words().len(), 15);
+    assert_eq!(generate_mnemonic(bip39::language::WordsCount::Words12, Language::English, None).unwrap().get_words().len(), 12);
 }  
 
+#[test]
+fn generate_new_mnemonic_with_rng() {
+    // Same seed, same RNG output : the generated mnemonic is reproducible
+    let mut rng = ChaCha20Rng::seed_from_u64(42);
+    let mnemonic = generate_mnemonic(WordsCount::Words24, Language::English, Some(&mut rng)).unwrap();
+
+    let mut rng = ChaCha20Rng::seed_from_u64(42);
+    assert_eq!(generate_mnemonic(WordsCount::Words24, Language::English, Some(&mut rng)).unwrap().get_phrase(), mnemonic.get_phrase());
+    assert_ne!(generate_mnemonic(WordsCount::Words24, Language::English, Some(&mut rng)).unwrap().get_phrase(), mnemonic.get_phrase());
+
+    let mut rng = ChaCha20Rng::seed_from_u64(7);
+    assert_ne!(generate_mnemonic(WordsCount::Words24, Language::English, Some(&mut rng)).unwrap().get_phrase(), mnemonic.get_phrase());
+}
+
 #[test]
 fn create_mnemonic_from_vectors() {
     // Vector from https://github.com/trezor/python-mnemonic/blob/master/vectors.json
@@ -195,7 +213,7 @@ fn create_mnemonic_from_vectors() {
         ),
     ];
 
-    for (entropy_hexa, mnemonic_phrase, seed_hex, _) in list.into_iter() {
+    for (entropy_hexa, mnemonic_phrase, seed_hex, xprv) in list.into_iter() {
         let current_mnemonic = Mnemonic::from_entropy(
             Entropy::from_hex(entropy_hexa.to_owned()).unwrap(),
             Language::English,
@@ -208,6 +226,100 @@ fn create_mnemonic_from_vectors() {
         );
         assert_eq!(mnemonic_phrase, current_mnemonic.to_string());
         assert_eq!(seed_hex, current_seed.to_hex());
+        assert_eq!(xprv, ExtendedPrivateKey::from_seed(&current_seed).unwrap().to_string());
+    }
+}
+
+#[test]
+fn create_seed_from_japanese_vectors_with_passphrase() {
+    // Vectors from https://github.com/bip32JP/bip32JP.github.io/blob/master/test_JP_BIP39.json
+    // The phrase is separated by ideographic spaces (U+3000) and the passphrase needs the NFKD normalization
+    let list = vec![
+        (
+            "00000000000000000000000000000000",
+            "あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あおぞら",
+            "㍍ガバヴァぱばぐゞちぢ十人十色",
+            "a262d6fb6122ecf45be09c50492b31f92e9beb7d9a845987a02cefda57a15f9c467a17872029a9e92299b5cbdf306e3a0ee620245cbd508959b6cb7ca637bd55"
+        ),
+        (
+            "000000000000000000000000000000000000000000000000",
+            "あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あらいぐま",
+            "㍍ガバヴァぱばぐゞちぢ十人十色",
+            "d99e8f1ce2d4288d30b9c815ae981edd923c01aa4ffdc5dee1ab5fe0d4a3e13966023324d119105aff266dac32e5cd11431eeca23bbd7202ff423f30d6776d69"
+        ),
+        (
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　らいう",
+            "㍍ガバヴァぱばぐゞちぢ十人十色",
+            "a44ba7054ac2f9226929d56505a51e13acdaa8a9097923ca07ea465c4c7e294c038f3f4e7e4b373726ba0057191aced6e48ac8d183f3a11569c426f0de414623"
+        ),
+        (
+            "77c2b00716cec7213839159e404db50d",
+            "せまい　うちがわ　あずき　かろう　めずらしい　だんち　ますく　おさめる　ていぼう　あたる　すあな　えしゃく",
+            "㍍ガバヴァぱばぐゞちぢ十人十色",
+            "344cef9efc37d0cb36d89def03d09144dd51167923487eec42c487f7428908546fa31a3c26b7391a2b3afe7db81b9f8c5007336b58e269ea0bd10749a87e0193"
+        ),
+        (
+            "3e141609b97933b66a060dcddc71fad1d91677db872031e85f4c015c5e7e8982",
+            "くのう　てぬぐい　そんかい　すろっと　ちきゅう　ほあん　とさか　はくしゅ　ひびく　みえる　そざい　てんすう　たんぴん　くしょう　すいようび　みけん　きさらぎ　げざん　ふくざつ　あつかう　はやい　くろう　おやゆび　こすう",
+            "㍍ガバヴァぱばぐゞちぢ十人十色",
+            "32e78dce2aff5db25aa7a4a32b493b5d10b4089923f3320c8b287a77e512455443298351beb3f7eb2390c4662a2e566eec5217e1a37467af43b46668d515e41b"
+        ),
+    ];
+
+    for (entropy_hexa, mnemonic_phrase, passphrase, seed_hex) in list.into_iter() {
+        let current_mnemonic = Mnemonic::from_entropy(
+            Entropy::from_hex(entropy_hexa.to_owned()).unwrap(),
+            Language::Japanese,
+        )
+        .unwrap();
+        assert_eq!(utils::to_utf8_nfkd(mnemonic_phrase.to_owned()), current_mnemonic.get_phrase());
+
+        let passphrase = Some(passphrase.to_owned());
+        assert_eq!(seed_hex, Seed::new(&mnemonic_phrase.to_owned(), &passphrase).to_hex());
+        assert_eq!(seed_hex, Seed::new(&current_mnemonic.get_phrase(), &passphrase).to_hex());
+        assert_eq!(seed_hex, get_seed_from_phrase(mnemonic_phrase.to_owned(), passphrase).to_hex());
+    }
+}
+
+#[test]
+fn create_mnemonic_from_japanese_vectors_file_with_any_separator() {
+    // Vectors from https://github.com/bip32JP/bip32JP.github.io/blob/master/test_JP_BIP39.json
+    let vectors: Vec<serde_json::Value> = serde_json::from_str(include_str!("vectors/test_JP_BIP39.json")).unwrap();
+    assert_eq!(vectors.len(), 24);
+
+    for vector in vectors {
+        let mnemonic_phrase = vector["mnemonic"].as_str().unwrap();
+        let passphrase = Some(vector["passphrase"].as_str().unwrap().to_owned());
+        let seed_hex = vector["seed"].as_str().unwrap();
+
+        let current_mnemonic = Mnemonic::from_entropy(
+            Entropy::from_hex(vector["entropy"].as_str().unwrap().to_owned()).unwrap(),
+            Language::Japanese,
+        )
+        .unwrap();
+        // Displayed with ideographic spaces, the words are NFKD
+        assert_eq!(current_mnemonic.to_string().nfc().collect::<String>(), mnemonic_phrase);
+        assert_eq!(seed_hex, get_seed_from_phrase(current_mnemonic.to_string(), passphrase.clone()).to_hex());
+        assert_eq!(seed_hex, get_seed_from_phrase(current_mnemonic.get_phrase(), passphrase.clone()).to_hex());
+
+        // The NFKD normalization replaces the ideographic spaces with ASCII spaces
+        let words = mnemonic_phrase.split('\u{3000}').collect::<Vec<&str>>();
+        assert_eq!(seed_hex, get_seed_from_phrase(mnemonic_phrase.to_owned(), passphrase.clone()).to_hex());
+        assert_eq!(seed_hex, get_seed_from_phrase(words.join(" "), passphrase.clone()).to_hex());
+
+        // Ideographic, ASCII or mixed whitespaces between the words are parsed to the same mnemonic
+        let mixed_phrase = words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| format!("{}{}", word, ["\u{3000}", " ", "\t", " \u{3000}"][i % 4]))
+            .collect::<String>();
+        for phrase in [mnemonic_phrase.to_owned(), words.join(" "), mixed_phrase] {
+            let mnemonic = Mnemonic::from_phrase(phrase, Language::Japanese).unwrap();
+            assert_eq!(mnemonic.get_entropy(), current_mnemonic.get_entropy());
+            assert_eq!(mnemonic.to_string(), current_mnemonic.to_string());
+            assert_eq!(seed_hex, get_seed_from_phrase(mnemonic.get_phrase(), passphrase.clone()).to_hex());
+        }
     }
 }
 
@@ -482,35 +594,35 @@ fn create_mnemonic_from_vectors_japanese() {
         (
             WordsCount::Words12,
             "6380163837921d15504675b5b988cc69",
-            "したみ あきる たりきほんがん すごい おくる たたみ けいれき ひめじし ぬかす ひつじゅひん きせつ ぷうたろう",
+            "したみ　あきる　たりきほんがん　すごい　おくる　たたみ　けいれき　ひめじし　ぬかす　ひつじゅひん　きせつ　ぷうたろう",
             "941530ba07db979119fa7c50218e3a19b404b3269018472f0a33887282c6bd8594660e621a7301959d5829f1d97b41cf583ea71dcdf6c48ba43d3bbc18c5c342",
             Language::Japanese
         ),
         (
             WordsCount::Words15,
             "21a4662f8df81397a60b40de57a19c201b491c07",
-            "おくりがな おじさん たにん えすて そぼろ ひたる ついたち しもん ほたて のせる しほん けいかく にんち たりきほんがん えんぎ",
+            "おくりがな　おじさん　たにん　えすて　そぼろ　ひたる　ついたち　しもん　ほたて　のせる　しほん　けいかく　にんち　たりきほんがん　えんぎ",
             "f1c00ce3275d5971eda50c037c2ca4b314f092eb659225bef5b850d81f714eb56b28ffd40e9f9321472cec859188b740aadf3a54d81a44946e0a0f264271f5aa",
             Language::Japanese
         ),
         (
             WordsCount::Words18,
             "a7c5f9b069546fa528f294c1b89d6986b3e1910d02b6cf97",
-            "とける きおう しらせる ふおん けまり ふえる でんち こねこね はくしゅ はっぽう さくひん いとこ くのう はんぶん きつつき こんすい ひりつ ねぼう",
+            "とける　きおう　しらせる　ふおん　けまり　ふえる　でんち　こねこね　はくしゅ　はっぽう　さくひん　いとこ　くのう　はんぶん　きつつき　こんすい　ひりつ　ねぼう",
             "db1678a8e0e984dadeb5cf2b8d1868ba7cb6f11e93128ed67ca3dbdef10648815e1cc1be84c79964cd92ae01380c85a2c09bdd4b24e77ef91baca16226ff2540",
             Language::Japanese
         ),
         (
             WordsCount::Words21,
             "c26bf7246667933e09b71783717a1e973686b7029516642359853c1b",
-            "ばしょ さんいん はんらん ぴっちり せんげん てさぎょう おんしゃ まんきつ そんみん たなばた たいら かんそう しゃおん ざっか いぜん てんかい しつもん えいぶん しあい せもたれ のぼる",
+            "ばしょ　さんいん　はんらん　ぴっちり　せんげん　てさぎょう　おんしゃ　まんきつ　そんみん　たなばた　たいら　かんそう　しゃおん　ざっか　いぜん　てんかい　しつもん　えいぶん　しあい　せもたれ　のぼる",
             "f24f64441443c646a72f1c6418f00dd77ed88ded7f1f697f22f6a515754b66c58f1c22047e44ca2d6af7d3dfcae800fa7113e201a5c46f1da8f331649bc89f75",
             Language::Japanese
         ),
         (
             WordsCount::Words24,
             "3fbdf874d961c78040356471ca73d5aa2c98291e6387aea4671fb39a497c9e01",
-            "ぐんしょく やたい えりあ にまめ えほん はいしん あけがた なこうど ずほう こぼれる よやく こむぎこ ひえる うしなう せんしゅ すばらしい なやむ おじさん ぱんつ しまう てんめつ のみもの せめる きおち",
+            "ぐんしょく　やたい　えりあ　にまめ　えほん　はいしん　あけがた　なこうど　ずほう　こぼれる　よやく　こむぎこ　ひえる　うしなう　せんしゅ　すばらしい　なやむ　おじさん　ぱんつ　しまう　てんめつ　のみもの　せめる　きおち",
             "566b518cf39f4d2254beee956c1fbc78d4b87538d9813e1573cfcf37e102e2a54336ea20b79709de8d745eeee5ad3cb39b42ff4b4b747ce738d7b92d89ce7eb0",
             Language::Japanese
         ),
@@ -563,12 +675,262 @@ fn create_mnemonic_from_vectors_korean() {
     create_mnemonic_from_vectors_language(list);
 }
 
+#[test]
+fn create_mnemonic_from_regression_vectors_chinese_simplified() {
+    // No published vectors for the chinese lists : regression vectors with arbitrary entropies, the phrases
+    // and the seeds (empty passphrase) are the output of the bip39 crate 2.2.2 (https://github.com/rust-bitcoin/rust-bip39)
+    let list = vec![
+        (
+            WordsCount::Words12,
+            "3a7c9e21b84f05d6c2e1a9077f4b8d63",
+            "层 罐 阴 伤 葡 捉 制 往 所 徙 酷 乃",
+            "decfc105aff45a802566bb5c697ccc390cfbd5da06882e61cbc56a8f69ce9e453d0e38064848612dfbc2afd20f385d41371a8755ae30c9ae493e6be73781b717",
+            Language::ChineseSimplified
+        ),
+        (
+            WordsCount::Words15,
+            "5f1e8c2a9b4d07e3c6a1f29b8e0d4c7a13f5b2e9",
+            "侵 刮 现 太 岭 慌 头 底 萨 希 射 闷 尔 偿 哲",
+            "ee9c516e3078a92b31952b9bddfd3556c7b4cd1cdcbfaaec8f3eea4f0f11817fde33de55df9138809f2e1d0d37bdbd7a0a493c3328e76a743dcee87e98b8fc39",
+            Language::ChineseSimplified
+        ),
+        (
+            WordsCount::Words18,
+            "c4a19e7f2b853d06e8f1a4c92d7b5e03f6a8c1d94b2e7f05",
+            "暂 好 轧 粉 限 床 睡 史 纠 谁 辞 十 兰 普 孩 龙 努 确",
+            "07bd9804860288b5fc7c3b9a01c1b00d18b75d64212a9eea30d53a3571fd65dc58f6edebe07fdb73e72d2bf21dcd3acfba6e171ebb1f0a1ee4f876629cf62492",
+            Language::ChineseSimplified
+        ),
+        (
+            WordsCount::Words21,
+            "81d3f6a92c4e07b5d8e1f3a6c9b24d7e05f8a1c3e6b92d4f7a0c5e8b",
+            "薄 尾 瑞 丝 盗 赋 啊 液 稍 财 吹 哀 久 古 平 饮 许 库 荷 桂 阔",
+            "bce8d0555bd72113d4bb1c19cfb2c209d45a60a4860c28b245204be914ef5deeecb7fa02161ab4da166179ac158a51fb60a8412a42c9e46df9f800ed0e3ad9d0",
+            Language::ChineseSimplified
+        ),
+        (
+            WordsCount::Words24,
+            "e7b2d94f1a6c83e05d2f7b9a4c1e6d803f5a2c7e9b1d4f6a08c3e5b7d92f4a16",
+            "御 废 限 劳 敏 歪 盟 傅 扎 普 秀 在 惠 立 箭 急 吸 抗 容 彼 险 婚 例 厅",
+            "ee2e77753f8cf2eaac7a3e8fa385c383f5668cdf36c2cc3e9970adde1a856ca42b8fe7603805887c3b77b51c2f1edf3ae7e546bdd6c49ca109e28a43a08ee1fe",
+            Language::ChineseSimplified
+        ),
+    ];
+
+    create_mnemonic_from_vectors_language(list);
+}
+
+#[test]
+fn create_mnemonic_from_regression_vectors_chinese_traditional() {
+    // No published vectors for the chinese lists : regression vectors with arbitrary entropies, the phrases
+    // and the seeds (empty passphrase) are the output of the bip39 crate 2.2.2 (https://github.com/rust-bitcoin/rust-bip39)
+    let list = vec![
+        (
+            WordsCount::Words12,
+            "3a7c9e21b84f05d6c2e1a9077f4b8d63",
+            "層 罐 陰 傷 葡 捉 制 往 所 徙 酷 乃",
+            "0c4a55a55b1c29fa61d65b12d0e1b58bb15022d5268d502f60dcf6739f3001915608335680b8146d16e7e4111b195d8da1ab636e48497e7032a30cf156522b51",
+            Language::ChineseTraditional
+        ),
+        (
+            WordsCount::Words15,
+            "5f1e8c2a9b4d07e3c6a1f29b8e0d4c7a13f5b2e9",
+            "侵 刮 現 太 嶺 慌 頭 底 薩 希 射 悶 爾 償 哲",
+            "0607b4b944ac5a5ab27b491829e96797d5b33ffc16de0c56a9878ac88bc25f6daf3b97e506b51e03f3b6d3004f30a42dfbc3825e7422175d13f901d6df8d213d",
+            Language::ChineseTraditional
+        ),
+        (
+            WordsCount::Words18,
+            "c4a19e7f2b853d06e8f1a4c92d7b5e03f6a8c1d94b2e7f05",
+            "暫 好 軋 粉 限 床 睡 史 糾 誰 辭 十 蘭 普 孩 龍 努 確",
+            "e3e9a8be6619799338f924d7439bf69233981edfd41474f70d1152251dc0b9c8139c2a5f16255a1df97ac57fa341d366b602975c775107643a0ceceb9d71c2ff",
+            Language::ChineseTraditional
+        ),
+        (
+            WordsCount::Words21,
+            "81d3f6a92c4e07b5d8e1f3a6c9b24d7e05f8a1c3e6b92d4f7a0c5e8b",
+            "薄 尾 瑞 絲 盜 賦 啊 液 稍 財 吹 哀 久 古 平 飲 許 庫 荷 桂 闊",
+            "18fb3b05715030a8101ae89c8ac0bdd45cfd0ff427a1bb30b92f35a3debd3c60e241bfa5220dfe38bdee1adef8e01f2e54073459876f41ce31ccf133afcc6abc",
+            Language::ChineseTraditional
+        ),
+        (
+            WordsCount::Words24,
+            "e7b2d94f1a6c83e05d2f7b9a4c1e6d803f5a2c7e9b1d4f6a08c3e5b7d92f4a16",
+            "禦 廢 限 勞 敏 歪 盟 傅 扎 普 秀 在 惠 立 箭 急 吸 抗 容 彼 險 婚 例 廳",
+            "bbee26dc77f7eb91b06b7cc8630c20062df6578f308c612bd6e198c4d58a46d3aae1eaa57b518c815ad3a82f49b33b87b55c995dbf5336adf9a8847f31300bea",
+            Language::ChineseTraditional
+        ),
+    ];
+
+    create_mnemonic_from_vectors_language(list);
+}
+
+#[test]
+fn create_mnemonic_from_chinese_phrase_without_spaces() {
+    let list = vec![
+        (Language::ChineseSimplified, "层罐阴伤葡捉制往所徙酷乃", "层 罐 阴 伤 葡 捉 制 往 所 徙 酷 乃"),
+        (Language::ChineseTraditional, "層罐陰傷葡捉制往所徙酷乃", "層 罐 陰 傷 葡 捉 制 往 所 徙 酷 乃"),
+    ];
+
+    for (lang, phrase_without_spaces, mnemonic_phrase) in list.into_iter() {
+        let current_mnemonic = Mnemonic::from_phrase(phrase_without_spaces.to_owned(), lang).unwrap();
+        assert_eq!(mnemonic_phrase, current_mnemonic.get_phrase());
+    }
+}
+
+#[test]
+fn compute_final_word_candidates() {
+    // (Chosen words, Candidates count, Expected candidate)
+    let list = vec![
+        ("abandon ".repeat(11), 128, "about"),
+        ("abandon ".repeat(14), 64, "address"),
+        ("abandon ".repeat(17), 32, "agent"),
+        ("abandon ".repeat(20), 16, "admit"),
+        ("abandon ".repeat(23), 8, "art"),
+        ("legal winner thank year wave sausage worth useful legal winner thank ".to_owned(), 128, "yellow"),
+    ];
+
+    for (partial_phrase, nb_candidates, final_word) in list {
+        let partial_words = partial_phrase.split_whitespace().map(str::to_owned).collect::<Vec<String>>();
+        let candidates = Mnemonic::final_word_candidates(&partial_words, Language::English, None).unwrap();
+
+        assert_eq!(candidates.len(), nb_candidates);
+        assert!(candidates.iter().any(|mnemonic| mnemonic.get_words().last().unwrap().as_str() == final_word));
+        for mnemonic in candidates.iter() {
+            assert!(Mnemonic::from_phrase(mnemonic.get_phrase(), Language::English).is_ok());
+            assert!(mnemonic.get_phrase().starts_with(partial_phrase.as_str()));
+        }
+    }
+}
+
+#[test]
+fn compute_final_word_candidates_with_supplemental_bits() {
+    let partial_words = vec!["zoo".to_owned(); 23];
+    let candidates = Mnemonic::final_word_candidates(&partial_words, Language::English, None).unwrap();
+    let mixed_candidates = Mnemonic::final_word_candidates(&partial_words, Language::English, Some(5)).unwrap();
+
+    // Same candidates, the first one has the supplemental bits as free entropy bits
+    assert_eq!(mixed_candidates[0].get_phrase(), candidates[5].get_phrase());
+    assert_eq!(mixed_candidates[5].get_phrase(), candidates[0].get_phrase());
+    assert!(Mnemonic::final_word_candidates(&partial_words, Language::English, Some(8)).is_err());
+}
+
+#[test]
+fn compute_final_word_candidates_should_err() {
+    assert_eq!(
+        Mnemonic::final_word_candidates(&vec!["abandon".to_owned(); 12], Language::English, None).unwrap_err(),
+        BIP32Error::InvalidWordsCount(12)
+    );
+    assert_eq!(
+        Mnemonic::final_word_candidates(&vec!["bitcoin".to_owned(); 11], Language::English, None).unwrap_err(),
+        BIP32Error::UnknownWord("bitcoin".to_owned())
+    );
+}
+
+#[test]
+fn create_mnemonic_from_abbreviated_phrase() {
+    // (Abbreviated phrase, Expected phrase, Language)
+    let list = vec![
+        (
+            "aban aban aban aban aban aban aban aban aban aban aban abou",
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            Language::English,
+        ),
+        (
+            "lega winn than year wave saus wort usef lega winn than yell",
+            "legal winner thank year wave sausage worth useful legal winner thank yellow",
+            Language::English,
+        ),
+        // Words shorter than 4 letters are written in full
+        (
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wron",
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong",
+            Language::English,
+        ),
+        (
+            "abai abai abai abai abai abai abai abai abai abai abai abei",
+            "abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abeille",
+            Language::French,
+        ),
+    ];
+
+    for (abbreviated_phrase, phrase, lang) in list {
+        let mnemonic = Mnemonic::from_abbreviated_phrase(abbreviated_phrase.to_owned(), lang.clone()).unwrap();
+        assert_eq!(mnemonic.get_phrase(), utils::to_utf8_nfkd(phrase.to_owned()));
+    }
+}
+
+#[test]
+fn create_mnemonic_from_abbreviated_phrase_should_err() {
+    assert_eq!(
+        Mnemonic::from_abbreviated_phrase("aban aban aban aban aban aban aban aban aban aban aban abo".to_owned(), Language::English).unwrap_err(),
+        BIP32Error::AmbiguousWord("abo".to_owned(), vec!["about".to_owned(), "above".to_owned()])
+    );
+    assert_eq!(
+        Mnemonic::from_abbreviated_phrase("aban aban aban aban aban aban aban aban aban aban aban bitc".to_owned(), Language::English).unwrap_err(),
+        BIP32Error::UnknownWord("bitc".to_owned())
+    );
+
+    // The 4 first letters are not enough to identify the words of this list
+    let english = bip39::language::Words::load(Language::English);
+    let mut lines = (0..2048).map(|index| english.get_words_from_index(&vec![index]).unwrap().remove(0)).collect::<Vec<String>>();
+    lines[1] = "abandoned".to_owned();
+    let custom = Language::Custom(std::sync::Arc::new(bip39::language::Words::from_lines(&lines.join("\n")).unwrap()));
+    assert_eq!(
+        Mnemonic::from_abbreviated_phrase("aban aban aban aban aban aban aban aban aban aban aban abou".to_owned(), custom).unwrap_err(),
+        BIP32Error::AmbiguousWord("aban".to_owned(), vec!["abandon".to_owned(), "abandoned".to_owned()])
+    );
+}
+
+#[test]
+fn translate_mnemonic_between_all_languages() {
+    let list = vec![
+        "00000000000000000000000000000000",
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "9e885d952ad362caeb4efe34a8e91bd2",
+        "f585c11aec520db57dd353c69554b21a89b20fb0650966fa0a9d6f74fd989d8f",
+    ];
+
+    for entropy_hex in list {
+        // The seeds only depend on the phrase, they are computed once per language
+        let mnemonics = Language::all()
+            .into_iter()
+            .map(|lang| Mnemonic::from_entropy(Entropy::from_hex(entropy_hex.to_owned()).unwrap(), lang).unwrap())
+            .collect::<Vec<Mnemonic>>();
+        let seeds = mnemonics.iter().map(|mnemonic| get_seed_from_phrase(mnemonic.get_phrase(), None).to_hex()).collect::<Vec<String>>();
+        let substrate_seed = mnemonics[0].to_substrate_seed("").to_hex();
+
+        for (from, mnemonic) in mnemonics.iter().enumerate() {
+            let from_lang = mnemonic.get_language();
+            assert_eq!(mnemonic.to_substrate_seed("").to_hex(), substrate_seed);
+
+            for (to, to_lang) in Language::all().into_iter().enumerate() {
+                let translated = mnemonic.to_language(to_lang.clone()).unwrap();
+                let round_trip = translated.to_language(from_lang.clone()).unwrap();
+
+                assert_eq!(translated.get_entropy(), mnemonic.get_entropy());
+                assert_eq!(translated.get_phrase(), mnemonics[to].get_phrase());
+                assert_eq!(translated.get_phrase(), Mnemonic::from_phrase(translated.get_phrase(), to_lang.clone()).unwrap().get_phrase());
+                assert_eq!(round_trip.get_phrase(), mnemonic.get_phrase());
+                assert!(round_trip.has_same_seed(mnemonic));
+
+                // The seed is computed from the words, a translation restores another wallet unless the words
+                // are the same (the chinese lists share most of their characters)
+                let same_seed = seeds[to] == seeds[from];
+                assert_eq!(translated.has_same_seed(mnemonic), same_seed);
+                assert_eq!(same_seed, translated.get_phrase() == mnemonic.get_phrase());
+                assert!(same_seed || from != to);
+            }
+        }
+    }
+}
+
 fn create_mnemonic_from_vectors_language(list: Vec<(WordsCount, &str, &str, &str, Language)>) {
     // Vectors validated from https://iancoleman.io/bip39
     for (words_count, entropy_hexa, mnemonic_phrase, seed_hex, lang) in list.into_iter() {
         let current_mnemonic = Mnemonic::from_entropy(
             Entropy::from_hex(entropy_hexa.to_owned()).unwrap(),
-            lang,
+            lang.clone(),
         )
         .unwrap();
 
@@ -577,6 +939,10 @@ fn create_mnemonic_from_vectors_language(list: Vec<(WordsCount, &str, &str, &str
             &None,
         );
         assert_eq!(mnemonic_phrase, current_mnemonic.to_string());
+
+        // Parse the phrase back and check we get the same mnemonic
+        let mnemonic_from_phrase = Mnemonic::from_phrase(mnemonic_phrase.to_owned(), lang).unwrap();
+        assert_eq!(current_mnemonic.get_phrase(), mnemonic_from_phrase.get_phrase());
         //Get EntropySizeFrom from usize and tranform into WordCount (can be simplified, I know :P)
         assert_eq!(WordsCount::from(current_mnemonic.get_words().len()), words_count);
         assert_eq!(seed_hex, current_seed.to_hex());
@@ -0,0 +1,122 @@
+use std::str::FromStr;
+
+use bip39::{bip85, BIP32Error, DerivationPath, ExtendedPrivateKey, Language, WordsCount};
+
+// Root key of the test vectors from https://github.com/bitcoin/bips/blob/master/bip-0085.mediawiki#test-vectors
+const ROOT_KEY: &str = "xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb";
+
+#[test]
+fn derive_entropy_from_vectors() {
+    // (Path, Derived key, Derived entropy)
+    let list = vec![
+        (
+            "m/83696968'/0'/0'",
+            "cca20ccb0e9a90feb0912870c3323b24874b0ca3d8018c4b96d0b97c0e82ded0",
+            "efecfbccffea313214232d29e71563d941229afb4338c21f9517c41aaa0d16f00b83d2a09ef747e7a64e8e2bd5a14869e693da66ce94ac2da570ab7ee48618f7",
+        ),
+        (
+            "m/83696968'/0'/1'",
+            "503776919131758bb7de7beb6c0ae24894f4ec042c26032890c29359216e21ba",
+            "70c6e3e8ebee8dc4c0dbba66076819bb8c09672527c4277ca8729532ad711872218f826919f6b67218adde99018a6df9095ab2b58d803b5b93ec9802085a690e",
+        ),
+    ];
+    let root = ExtendedPrivateKey::from_str(ROOT_KEY).unwrap();
+
+    for (path, derived_key, derived_entropy) in list {
+        let path = DerivationPath::from_str(path).unwrap();
+
+        assert_eq!(
+            root.derive_path(&path).unwrap().private_key().to_hex(),
+            derived_key
+        );
+        assert_eq!(
+            bip85::derive_entropy(&root, &path).unwrap().to_hex(),
+            derived_entropy
+        );
+    }
+}
+
+#[test]
+fn derive_mnemonic_from_vectors() {
+    // (Words count, Derived entropy, Derived mnemonic)
+    let list = vec![
+        (
+            WordsCount::Words12,
+            "6250b68daf746d12a24d58b4787a714b",
+            "girl mad pet galaxy egg matter matrix prison refuse sense ordinary nose",
+        ),
+        (
+            WordsCount::Words18,
+            "938033ed8b12698449d4bbca3c853c66b293ea1b1ce9d9dc",
+            "near account window bike charge season chef number sketch tomorrow excuse sniff circle vital hockey outdoor supply token",
+        ),
+        (
+            WordsCount::Words24,
+            "ae131e2312cdc61331542efe0d1077bac5ea803adf24b313a4f0e48e9c51f37f",
+            "puppy ocean match cereal symbol another shed magic wrap hammer bulb intact gadget divorce twin tonight reason outdoor destroy simple truth cigar social volcano",
+        ),
+    ];
+    let root = ExtendedPrivateKey::from_str(ROOT_KEY).unwrap();
+
+    for (nb_words, derived_entropy, derived_mnemonic) in list {
+        let path =
+            DerivationPath::from_str(&format!("m/83696968'/39'/0'/{}'/0'", nb_words.nb_words()))
+                .unwrap();
+        assert_eq!(
+            hex::encode(
+                &bip85::derive_entropy(&root, &path).unwrap().as_ref()[..derived_entropy.len() / 2]
+            ),
+            derived_entropy
+        );
+
+        let mnemonic = bip85::to_mnemonic(&root, Language::English, nb_words, 0).unwrap();
+        assert_eq!(mnemonic.get_phrase(), derived_mnemonic);
+    }
+}
+
+#[test]
+fn derive_mnemonic_in_other_languages() {
+    let root = ExtendedPrivateKey::from_str(ROOT_KEY).unwrap();
+
+    let english = bip85::to_mnemonic(&root, Language::English, WordsCount::Words12, 0).unwrap();
+    let french = bip85::to_mnemonic(&root, Language::French, WordsCount::Words12, 0).unwrap();
+    let second = bip85::to_mnemonic(&root, Language::English, WordsCount::Words12, 1).unwrap();
+
+    // The child mnemonic is a valid French mnemonic, and each index gives another mnemonic
+    assert_eq!(french.get_words().len(), 12);
+    assert_ne!(english.get_phrase(), second.get_phrase());
+    assert!(bip39::Mnemonic::from_phrase(french.get_phrase(), Language::French).is_ok());
+}
+
+#[test]
+fn derive_hex_wif_xprv_from_vectors() {
+    let root = ExtendedPrivateKey::from_str(ROOT_KEY).unwrap();
+
+    assert_eq!(
+        bip85::to_wif(&root, 0).unwrap().as_str(),
+        "Kzyv4uF39d4Jrw2W7UryTHwZr1zQVNk4dAFyqE6BuMrMh1Za7uhp"
+    );
+    assert_eq!(
+        bip85::to_xprv(&root, 0).unwrap().to_string(),
+        "xprv9s21ZrQH143K2srSbCSg4m4kLvPMzcWydgmKEnMmoZUurYuBuYG46c6P71UGXMzmriLzCCBvKQWBUv3vPB3m1SATMhp3uEjXHJ42jFg7myX"
+    );
+    assert_eq!(
+        bip85::to_hex(&root, 64, 0).unwrap().to_hex(),
+        "492db4698cf3b73a5a24998aa3e9d7fa96275d85724a91e71aa2d645442f878555d078fd1f1f67e368976f04137b1f7a0d19232136ca50c44614af72b5582a5c"
+    );
+}
+
+#[test]
+fn derive_hex_with_invalid_length_should_err() {
+    let root = ExtendedPrivateKey::from_str(ROOT_KEY).unwrap();
+
+    assert!(matches!(
+        bip85::to_hex(&root, 15, 0),
+        Err(BIP32Error::InvalidBIP85Parameter(_))
+    ));
+    assert!(matches!(
+        bip85::to_hex(&root, 65, 0),
+        Err(BIP32Error::InvalidBIP85Parameter(_))
+    ));
+    assert_eq!(bip85::to_hex(&root, 16, 0).unwrap().nb_bytes(), 16);
+}
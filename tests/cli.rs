@@ -0,0 +1,105 @@
+#![cfg(feature = "cli")]
+
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+// Vector from https://github.com/trezor/python-mnemonic/blob/master/vectors.json, the passphrase is "TREZOR"
+const PHRASE: &str = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+const ENTROPY: &str = "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f";
+const SEED: &str = "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607";
+
+///
+/// Run the binary with the arguments and the standard input, returns (success, stdout, stderr)
+///
+fn run(args: &[&str], stdin: &str) -> (bool, String, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_bip39"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    (
+        output.status.success(),
+        String::from_utf8(output.stdout)
+            .unwrap()
+            .trim_end()
+            .to_owned(),
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .trim_end()
+            .to_owned(),
+    )
+}
+
+#[test]
+fn run_commands() {
+    let stdin = format!("{}\n", PHRASE);
+    // (Arguments, Standard input, Expected output)
+    let list = vec![
+        (vec!["validate"], stdin.clone(), "valid: true\nlanguage: english\nwords: 12".to_owned()),
+        (vec!["--json", "validate"], stdin.clone(), r#"{"valid":true,"language":"english","words":12}"#.to_owned()),
+        (vec!["entropy"], stdin.clone(), ENTROPY.to_owned()),
+        (vec!["entropy", "--lang", "english"], stdin.clone(), ENTROPY.to_owned()),
+        (vec!["from-entropy"], format!("{}\n", ENTROPY), PHRASE.to_owned()),
+        (vec!["seed", "--passphrase-stdin"], format!("{}\nTREZOR\n", PHRASE), SEED.to_owned()),
+        (vec!["--json", "seed", "--passphrase-stdin"], format!("{}\r\nTREZOR", PHRASE), format!(r#"{{"seed":"{}"}}"#, SEED)),
+        (
+            vec!["translate", "--to", "italian"],
+            stdin.clone(),
+            "phrase: mimosa vita sussurro zinco vero saltare zattera ulisse mimosa vita sussurro zircone\nlanguage: italian\nsame_seed: false".to_owned(),
+        ),
+    ];
+
+    for (args, stdin, expected_output) in list {
+        let (success, output, _) = run(&args, &stdin);
+        assert!(success);
+        assert_eq!(output, expected_output);
+    }
+}
+
+#[test]
+fn run_generate_command() {
+    let (success, output, _) = run(
+        &["--json", "generate", "--words", "18", "--lang", "spanish"],
+        "",
+    );
+    assert!(success);
+    assert!(output.ends_with(r#","language":"spanish","words":18}"#));
+
+    let (success, output, _) = run(&["generate"], "");
+    assert!(success);
+    assert_eq!(output.lines().next().unwrap().split(' ').count(), 25);
+}
+
+#[test]
+fn run_invalid_commands_should_err() {
+    // (Arguments, Standard input, Expected error)
+    let list = vec![
+        (vec!["validate"], "legal winner thank year wave sausage worth useful legal winner thank thank\n", "Error : The mnemonic checksum is not valid"),
+        (vec!["validate"], "", "Error : No mnemonic given on the standard input"),
+        (vec!["from-entropy"], "7f7f\n", "Error : Entropy not valid. It should be more than 128 bits, less than 256 bits and be a multiple of 32"),
+        (vec!["generate", "--words", "13"], "", "Error : The words count (13) is not valid"),
+    ];
+
+    for (args, stdin, expected_error) in list {
+        let (success, output, error) = run(&args, stdin);
+        assert!(!success);
+        assert!(output.is_empty());
+        assert_eq!(error, expected_error);
+    }
+
+    let (success, output, _) = run(&["--json", "entropy"], "bitcoin\n");
+    assert!(!success);
+    assert!(output.starts_with(r#"{"error":"#));
+}
@@ -0,0 +1,130 @@
+use bip39::{
+    generate_mnemonic_from_entropy, get_seed_from_phrase, Entropy, ExtendedPrivateKey, Language,
+    Mnemonic,
+};
+use unicode_normalization::UnicodeNormalization;
+
+///
+/// Official vector : entropy -> mnemonic -> seed (with the passphrase) -> BIP32 root key
+///
+struct Vector {
+    lang: Language,
+    entropy: String,
+    mnemonic: String,
+    passphrase: String,
+    seed: String,
+    xprv: String,
+}
+
+///
+/// Load the vectors from https://github.com/trezor/python-mnemonic/blob/master/vectors.json,
+/// each language has a list of [entropy, mnemonic, seed, xprv] and the passphrase is "TREZOR"
+///
+fn trezor_vectors() -> Vec<Vector> {
+    let vectors: serde_json::Value =
+        serde_json::from_str(include_str!("vectors/vectors.json")).unwrap();
+
+    vectors
+        .as_object()
+        .unwrap()
+        .iter()
+        .flat_map(|(lang_name, list)| {
+            let lang = Language::all()
+                .into_iter()
+                .find(|lang| format!("{:?}", lang).to_lowercase() == *lang_name)
+                .unwrap();
+
+            list.as_array().unwrap().iter().map(move |vector| Vector {
+                lang: lang.clone(),
+                entropy: vector[0].as_str().unwrap().to_owned(),
+                mnemonic: vector[1].as_str().unwrap().to_owned(),
+                passphrase: "TREZOR".to_owned(),
+                seed: vector[2].as_str().unwrap().to_owned(),
+                xprv: vector[3].as_str().unwrap().to_owned(),
+            })
+        })
+        .collect()
+}
+
+///
+/// Load the vectors from https://github.com/bip32JP/bip32JP.github.io/blob/master/test_JP_BIP39.json,
+/// the phrases are separated by ideographic spaces and the passphrase needs the NFKD normalization
+///
+fn japanese_vectors() -> Vec<Vector> {
+    let vectors: Vec<serde_json::Value> =
+        serde_json::from_str(include_str!("vectors/test_JP_BIP39.json")).unwrap();
+
+    vectors
+        .iter()
+        .map(|vector| Vector {
+            lang: Language::Japanese,
+            entropy: vector["entropy"].as_str().unwrap().to_owned(),
+            mnemonic: vector["mnemonic"].as_str().unwrap().to_owned(),
+            passphrase: vector["passphrase"].as_str().unwrap().to_owned(),
+            seed: vector["seed"].as_str().unwrap().to_owned(),
+            xprv: vector["bip32_xprv"].as_str().unwrap().to_owned(),
+        })
+        .collect()
+}
+
+fn check_vector(vector: &Vector) {
+    // Entropy -> mnemonic, the displayed words are compared in NFC as in the vectors files
+    let mnemonic = generate_mnemonic_from_entropy(
+        Entropy::from_hex(vector.entropy.clone()).unwrap(),
+        vector.lang.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        mnemonic.to_string().nfc().collect::<String>(),
+        vector.mnemonic,
+        "entropy {}",
+        vector.entropy
+    );
+    assert_eq!(
+        mnemonic.get_words().len(),
+        vector.entropy.len() * 4 * 3 / 32
+    );
+
+    // Mnemonic -> entropy
+    let parsed_mnemonic =
+        Mnemonic::from_phrase(vector.mnemonic.clone(), vector.lang.clone()).unwrap();
+    assert_eq!(
+        parsed_mnemonic.get_entropy().entropy.to_hex(),
+        vector.entropy
+    );
+    assert_eq!(parsed_mnemonic.get_phrase(), mnemonic.get_phrase());
+
+    // Mnemonic -> seed, from the generated and from the written phrases
+    let seed = get_seed_from_phrase(mnemonic.get_phrase(), Some(vector.passphrase.clone()));
+    assert_eq!(seed.to_hex(), vector.seed, "mnemonic {}", vector.mnemonic);
+    assert_eq!(
+        get_seed_from_phrase(vector.mnemonic.clone(), Some(vector.passphrase.clone())).to_hex(),
+        vector.seed
+    );
+
+    // Seed -> BIP32 root key
+    assert_eq!(
+        ExtendedPrivateKey::from_seed(&seed).unwrap().to_string(),
+        vector.xprv
+    );
+}
+
+#[test]
+fn run_trezor_vectors() {
+    let vectors = trezor_vectors();
+    assert_eq!(vectors.len(), 24);
+
+    for vector in vectors.iter() {
+        check_vector(vector);
+    }
+}
+
+#[test]
+fn run_japanese_vectors() {
+    let vectors = japanese_vectors();
+    assert_eq!(vectors.len(), 24);
+
+    for vector in vectors.iter() {
+        check_vector(vector);
+    }
+}
@@ -0,0 +1,106 @@
+use std::{
+    str::FromStr,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+};
+
+use bip39::{
+    passphrase_recovery::{PassphraseGenerator, PassphraseRecovery},
+    recovery::{Progress, RecoveryTarget},
+    BIP32Error, DerivationPath, ExtendedPrivateKey, Language, Mnemonic,
+};
+
+// Vector from https://github.com/trezor/python-mnemonic/blob/master/vectors.json, the passphrase is "TREZOR"
+const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+const ROOT_KEY: &str = "xprv9s21ZrQH143K3h3fDYiay8mocZ3afhfULfb5GX8kCBdno77K4HiA15Tg23wpbeF1pLfs1c5SPmYHrEpTuuRhxMwvKDwqdKiGJS9XFKzUsAF";
+
+fn targets() -> Vec<RecoveryTarget> {
+    let root = ExtendedPrivateKey::from_str(ROOT_KEY).unwrap();
+    let account_path = DerivationPath::from_str("m/84'/0'/0'").unwrap();
+
+    vec![
+        RecoveryTarget::Fingerprint(root.fingerprint()),
+        RecoveryTarget::ExtendedPublicKey(
+            account_path.clone(),
+            root.derive_path(&account_path).unwrap().to_extended_public_key(),
+        ),
+    ]
+}
+
+#[test]
+fn recover_passphrase_from_generators() {
+    let mnemonic = Mnemonic::from_phrase(PHRASE.to_owned(), Language::English).unwrap();
+    // (Generator, Search space)
+    let list: Vec<(fn() -> PassphraseGenerator, u64)> = vec![
+        (|| PassphraseGenerator::dictionary(vec!["trezor".to_owned(), "Trezor".to_owned(), "TREZOR".to_owned()]), 3),
+        (|| PassphraseGenerator::mask("TREZ?uR").unwrap(), 26),
+        (|| PassphraseGenerator::typos("TERZOR"), 1236),
+    ];
+
+    for (generator, search_space) in list {
+        for target in targets() {
+            let recovery = PassphraseRecovery::new(&mnemonic, target, generator());
+
+            assert_eq!(recovery.search_space(), search_space);
+            assert_eq!(recovery.run(None, None).unwrap().unwrap().as_str(), "TREZOR");
+        }
+    }
+}
+
+#[test]
+fn recover_passphrase_not_found() {
+    let mnemonic = Mnemonic::from_phrase(PHRASE.to_owned(), Language::English).unwrap();
+    let generator = PassphraseGenerator::dictionary(vec!["".to_owned(), "trezor".to_owned()]);
+    let recovery = PassphraseRecovery::new(&mnemonic, targets().remove(0), generator);
+
+    assert!(recovery.run(None, None).unwrap().is_none());
+}
+
+#[test]
+fn recover_passphrase_with_progress_and_cancel() {
+    let mnemonic = Mnemonic::from_phrase(PHRASE.to_owned(), Language::English).unwrap();
+    let recovery = PassphraseRecovery::new(
+        &mnemonic,
+        targets().remove(0),
+        PassphraseGenerator::mask("?d?d?d?d").unwrap(),
+    )
+    .with_threads(2);
+    let cancel = AtomicBool::new(false);
+    let checked = AtomicU64::new(0);
+
+    // Cancel once the first batches are checked
+    let progress = |progress: Progress| {
+        assert_eq!(progress.total, 10_000);
+        assert!(progress.throughput() > 0.0);
+        checked.fetch_max(progress.checked, Ordering::Relaxed);
+        cancel.store(true, Ordering::Relaxed);
+    };
+
+    assert_eq!(recovery.run(Some(&progress), Some(&cancel)).unwrap_err(), BIP32Error::RecoveryCancelled);
+    assert!(checked.load(Ordering::Relaxed) < 10_000);
+}
+
+#[test]
+fn recover_passphrase_cancelled_after_the_last_batch() {
+    let mnemonic = Mnemonic::from_phrase(PHRASE.to_owned(), Language::English).unwrap();
+    let generator = PassphraseGenerator::dictionary(vec!["".to_owned(), "trezor".to_owned()]);
+    let recovery = PassphraseRecovery::new(&mnemonic, targets().remove(0), generator).with_threads(1);
+    let cancel = AtomicBool::new(false);
+
+    // The whole search space is checked before the cancel flag is set
+    let progress = |_: Progress| cancel.store(true, Ordering::Relaxed);
+
+    assert!(recovery.run(Some(&progress), Some(&cancel)).unwrap().is_none());
+}
+
+#[test]
+fn recover_passphrase_with_failing_target_should_err() {
+    let mnemonic = Mnemonic::from_phrase(PHRASE.to_owned(), Language::English).unwrap();
+    // Deriving 256 levels exceeds the maximum depth
+    let target = RecoveryTarget::ExtendedPublicKey(
+        DerivationPath::from_str(&format!("m{}", "/0".repeat(256))).unwrap(),
+        ExtendedPrivateKey::from_str(ROOT_KEY).unwrap().to_extended_public_key(),
+    );
+    let recovery = PassphraseRecovery::new(&mnemonic, target, PassphraseGenerator::mask("?d?d?d?d").unwrap());
+
+    assert_eq!(recovery.run(None, None).unwrap_err(), BIP32Error::MaxDepthExceeded);
+}
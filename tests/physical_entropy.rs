@@ -0,0 +1,155 @@
+use std::str::FromStr;
+
+use bip39::{entropy::EntropySize, BIP32Error, Card, Entropy, Language, Mnemonic};
+
+#[test]
+fn create_mnemonic_from_dice_rolls() {
+    // (Rolls, Entropy size, Mnemonic), computed as Coldcard and SeedSigner do : SHA256 of the rolls digits, truncated
+    let list = vec![
+        (
+            "1234561234561234561234561234561234561234561234561234561234561234561234561234561234561234561234561234",
+            EntropySize::Bits256,
+            "tornado cactus wheel picture target finish home neither trend picture shoulder endless deputy glide open oxygen another ability forum swear side alcohol devote random",
+        ),
+        (
+            "66666666666666666666666666666666666666666666666666",
+            EntropySize::Bits128,
+            "senior morning song proud recycle toy search apple trigger lend vibrant arrest",
+        ),
+    ];
+
+    for (rolls, entropy_size, phrase) in list {
+        let rolls = rolls.bytes().map(|roll| roll - b'0').collect::<Vec<u8>>();
+        let entropy = Entropy::from_dice_rolls(&rolls, 6, entropy_size).unwrap();
+
+        assert_eq!(
+            Mnemonic::from_entropy(entropy, Language::English).unwrap().get_phrase(),
+            phrase
+        );
+    }
+}
+
+#[test]
+fn create_entropy_from_not_enough_dice_rolls_should_err() {
+    // As Coldcard and SeedSigner, 50 rolls of a 6 sides dice for 12 words and 99 rolls for 24 words
+    assert!(Entropy::from_dice_rolls(&[3; 99], 6, EntropySize::Bits256).is_ok());
+    assert!(Entropy::from_dice_rolls(&[3; 50], 6, EntropySize::Bits128).is_ok());
+    assert_eq!(
+        Entropy::from_dice_rolls(&[3; 98], 6, EntropySize::Bits256),
+        Err(BIP32Error::InsufficientEntropy(253, 256))
+    );
+    assert_eq!(
+        Entropy::from_dice_rolls(&[3; 49], 6, EntropySize::Bits128),
+        Err(BIP32Error::InsufficientEntropy(126, 128))
+    );
+
+    // A 20 sides dice carries 4.32 bits per roll
+    assert!(Entropy::from_dice_rolls(&[20; 29], 20, EntropySize::Bits128).is_err());
+    assert!(Entropy::from_dice_rolls(&[20; 30], 20, EntropySize::Bits128).is_ok());
+}
+
+#[test]
+fn create_entropy_from_dice_rolls_strict() {
+    // 99 rolls of a 6 sides dice only carry 255.9 bits
+    assert_eq!(
+        Entropy::from_dice_rolls_strict(&[3; 99], 6, EntropySize::Bits256),
+        Err(BIP32Error::InsufficientEntropy(255, 256))
+    );
+    assert_eq!(
+        Entropy::from_dice_rolls_strict(&[3; 100], 6, EntropySize::Bits256),
+        Entropy::from_dice_rolls(&[3; 100], 6, EntropySize::Bits256)
+    );
+    assert!(Entropy::from_dice_rolls_strict(&[3; 50], 6, EntropySize::Bits128).is_ok());
+}
+
+#[test]
+fn create_entropy_from_invalid_dice_rolls_should_err() {
+    let list = vec![(vec![1, 7, 2], 6), (vec![0, 1, 2], 6), (vec![1, 1, 1], 1)];
+
+    for (rolls, sides) in list {
+        assert!(matches!(
+            Entropy::from_dice_rolls(&rolls, sides, EntropySize::Bits128),
+            Err(BIP32Error::InvalidEntropySource(_))
+        ));
+    }
+}
+
+#[test]
+fn create_mnemonic_from_coin_flips() {
+    let flips = (0..128).map(|i| i % 2 == 0).collect::<Vec<bool>>();
+    let entropy = Entropy::from_coin_flips(&flips, EntropySize::Bits128).unwrap();
+
+    assert_eq!(
+        Mnemonic::from_entropy(entropy, Language::English).unwrap().get_phrase(),
+        "blanket comfort model weekend box scatter busy board ghost oval purchase vapor"
+    );
+    assert_eq!(
+        Entropy::from_coin_flips(&flips[..127], EntropySize::Bits128),
+        Err(BIP32Error::InsufficientEntropy(127, 128))
+    );
+}
+
+#[test]
+fn create_mnemonic_from_card_deck() {
+    let deck = "AS 2S 3S 4S 5S 6S 7S 8S 9S TS JS QS KS AH 2H 3H 4H 5H 6H 7H 8H 9H TH JH QH KH \
+                AD 2D 3D 4D 5D 6D 7D 8D 9D TD JD QD KD AC 2C 3C 4C 5C 6C 7C 8C 9C TC JC QC KC"
+        .split_whitespace()
+        .map(|card| Card::from_str(card).unwrap())
+        .collect::<Vec<Card>>();
+
+    // A full deck carries 225 bits, enough for 21 words but not for 24 words
+    let entropy = Entropy::from_card_deck(&deck, EntropySize::Bits224).unwrap();
+    assert_eq!(
+        Mnemonic::from_entropy(entropy, Language::English).unwrap().get_phrase(),
+        "price artefact noise brother vault favorite spice now wealth paper august six size sorry job topple switch various butter wear mystery"
+    );
+    assert_eq!(
+        Entropy::from_card_deck(&deck, EntropySize::Bits256),
+        Err(BIP32Error::InsufficientEntropy(225, 256))
+    );
+
+    // 23 cards without replacement carry 122.8 bits
+    assert_eq!(
+        Entropy::from_card_deck(&deck[..23], EntropySize::Bits128),
+        Err(BIP32Error::InsufficientEntropy(122, 128))
+    );
+    assert!(Entropy::from_card_deck(&deck[..25], EntropySize::Bits128).is_ok());
+}
+
+#[test]
+fn create_entropy_from_card_deck_with_duplicates_should_err() {
+    let mut cards = "AS 2S 3S 4S 5S 6S 7S 8S 9S TS JS QS KS AH 2H 3H 4H 5H 6H 7H 8H 9H TH JH QH KH"
+        .split_whitespace()
+        .map(|card| Card::from_str(card).unwrap())
+        .collect::<Vec<Card>>();
+    cards.push(cards[3]);
+
+    assert_eq!(
+        Entropy::from_card_deck(&cards, EntropySize::Bits128),
+        Err(BIP32Error::InvalidEntropySource(
+            "the card 4S is present several times in the shuffled deck".to_owned()
+        ))
+    );
+}
+
+#[test]
+fn create_mnemonic_from_card_draws() {
+    // Drawn with replacement, each card carries log2(52) = 5.7 bits
+    let draws = vec![Card::from_str("QH").unwrap(); 45];
+
+    assert!(Entropy::from_card_draws(&draws, EntropySize::Bits256).is_ok());
+    assert_eq!(
+        Entropy::from_card_draws(&draws[..44], EntropySize::Bits256),
+        Err(BIP32Error::InsufficientEntropy(250, 256))
+    );
+
+    // Same record as a shuffled deck, so the same entropy
+    let deck = "AS 2S 3S 4S 5S 6S 7S 8S 9S TS JS QS KS AH 2H 3H 4H 5H 6H 7H 8H 9H TH JH QH"
+        .split_whitespace()
+        .map(|card| Card::from_str(card).unwrap())
+        .collect::<Vec<Card>>();
+    assert_eq!(
+        Entropy::from_card_draws(&deck, EntropySize::Bits128),
+        Entropy::from_card_deck(&deck, EntropySize::Bits128)
+    );
+}
@@ -0,0 +1,229 @@
+use std::{
+    str::FromStr,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use bip39::{
+    recovery::{self, Progress, RecoveryTarget, Transposition, WordRecovery},
+    BIP32Error, DerivationPath, ExtendedPrivateKey, Language, Mnemonic,
+};
+
+// Vector from https://github.com/trezor/python-mnemonic/blob/master/vectors.json
+const PHRASE: &str = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+const PHRASE_2: &str = "letter advice cage absurd amount doctor acoustic avoid letter advice cage above";
+const ABANDON_PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+// The passphrase is "TREZOR"
+const ART_PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
+const ART_ROOT_KEY: &str = "xprv9s21ZrQH143K32qBagUJAMU2LsHg3ka7jqMcV98Y7gVeVyNStwYS3U7yVVoDZ4btbRNf4h6ibWpY22iRmXq35qgLs79f312g2kj5539ebPM";
+
+#[test]
+fn recover_wrong_or_missing_word() {
+    // (Written phrase, Expected distance of the phrase)
+    let list = vec![
+        // Unknown word
+        ("legal winner ? year wave sausage worth useful legal winner thank yellow", 0),
+        // Word not in the list, "m" is next to "n" on the keyboard
+        ("legal winner thamk year wave sausage worth useful legal winner thank yellow", 1),
+        // Swapped letters
+        ("legal winner thank yaer wave sausage worth useful legal winner thank yellow", 1),
+        // Lost word
+        ("legal winner thank year wave sausage worth useful legal winner yellow", 0),
+        // Valid word but invalid checksum, "s" is next to "w" on the keyboard
+        ("legal winner thank year save sausage worth useful legal winner thank yellow", 1),
+    ];
+
+    for (written_phrase, distance) in list {
+        let recovery = WordRecovery::new(written_phrase, Language::English).unwrap();
+        let candidates = recovery.run(None).unwrap();
+
+        let candidate = candidates
+            .iter()
+            .find(|candidate| candidate.mnemonic().get_phrase() == PHRASE)
+            .unwrap();
+        assert_eq!(candidate.distance(), distance);
+        assert!(candidates.iter().all(|candidate| candidate.distance() >= distance));
+        assert!(candidates.windows(2).all(|pair| pair[0].distance() <= pair[1].distance()));
+    }
+}
+
+#[test]
+fn recover_misspelled_word_ranked_first() {
+    let recovery = WordRecovery::new(
+        "legal winner thamk year wave sausage worth useful legal winner thank yellow",
+        Language::English,
+    )
+    .unwrap();
+    let candidates = recovery.run(None).unwrap();
+
+    assert_eq!(recovery.search_space(), 2048);
+    assert_eq!(candidates[0].mnemonic().get_phrase(), PHRASE);
+}
+
+#[test]
+fn recover_word_with_target() {
+    // The 24 words phrase has an 8 bits checksum, about 8 candidates are derived to be compared with the target
+    let root = ExtendedPrivateKey::from_str(ART_ROOT_KEY).unwrap();
+    let account_path = DerivationPath::from_str("m/84'/0'/0'").unwrap();
+    let list = vec![
+        RecoveryTarget::Fingerprint(root.fingerprint()),
+        RecoveryTarget::ExtendedPublicKey(
+            account_path.clone(),
+            root.derive_path(&account_path).unwrap().to_extended_public_key(),
+        ),
+    ];
+
+    for target in list {
+        let recovery = WordRecovery::new(
+            &ART_PHRASE.replacen("abandon", "?", 1),
+            Language::English,
+        )
+        .unwrap()
+        .with_passphrase(Some("TREZOR".to_owned()))
+        .with_target(target);
+        let candidates = recovery.run(None).unwrap();
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].mnemonic().get_phrase(), ART_PHRASE);
+    }
+}
+
+#[test]
+fn target_is_matching() {
+    // Master fingerprint and first addresses of BIP44 and BIP84 accounts
+    let list = vec![
+        RecoveryTarget::Fingerprint([0x73, 0xc5, 0xda, 0x0a]),
+        RecoveryTarget::Address(
+            DerivationPath::from_str("m/44'/0'/0'/0/0").unwrap(),
+            "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA".to_owned(),
+        ),
+        RecoveryTarget::Address(
+            DerivationPath::from_str("m/84'/0'/0'/0/0").unwrap(),
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu".to_owned(),
+        ),
+    ];
+
+    let mnemonic = Mnemonic::from_phrase(ABANDON_PHRASE.to_owned(), Language::English).unwrap();
+    let other = Mnemonic::from_phrase(PHRASE.to_owned(), Language::English).unwrap();
+
+    for target in list {
+        assert!(target.is_matching(&mnemonic, &None).unwrap());
+        assert!(!target.is_matching(&other, &None).unwrap());
+        assert!(!target.is_matching(&mnemonic, &Some("TREZOR".to_owned())).unwrap());
+    }
+}
+
+#[test]
+#[ignore = "checks 4 million phrases, run with cargo test --release -- --ignored"]
+fn recover_two_unknown_words_with_progress() {
+    let recovery = WordRecovery::new(
+        "legal winner thank year wave ? worth useful legal ? thank yellow",
+        Language::English,
+    )
+    .unwrap()
+    .with_threads(4);
+    let checked = AtomicU64::new(0);
+    let progress = |progress: Progress| {
+        assert_eq!(progress.total, 2048 * 2048);
+        checked.fetch_max(progress.checked, Ordering::Relaxed);
+    };
+
+    let candidates = recovery.run(Some(&progress)).unwrap();
+
+    assert_eq!(recovery.search_space(), 2048 * 2048);
+    assert_eq!(checked.load(Ordering::Relaxed), 2048 * 2048);
+    // About 1 / 16 of the phrases have a valid checksum
+    assert!((200_000..320_000).contains(&candidates.len()));
+    assert!(candidates.iter().any(|candidate| candidate.mnemonic().get_phrase() == PHRASE));
+}
+
+#[test]
+fn recover_with_failing_target_should_err() {
+    // Deriving 256 levels exceeds the maximum depth, the first candidate with a valid checksum stops the search
+    let path = DerivationPath::from_str(&format!("m{}", "/0".repeat(256))).unwrap();
+    let recovery = WordRecovery::new(
+        "legal winner thank year wave ? worth useful legal ? thank yellow",
+        Language::English,
+    )
+    .unwrap()
+    .with_target(RecoveryTarget::ExtendedPublicKey(
+        path,
+        ExtendedPrivateKey::from_str(ART_ROOT_KEY).unwrap().to_extended_public_key(),
+    ))
+    .with_threads(4);
+
+    assert_eq!(recovery.run(None).err(), Some(BIP32Error::MaxDepthExceeded));
+}
+
+#[test]
+fn recover_invalid_phrase_should_err() {
+    assert_eq!(
+        WordRecovery::new("? winner ? year wave ? worth useful legal winner thank yellow", Language::English).err(),
+        Some(BIP32Error::TooManyUnknownWords(3))
+    );
+    // Lost word and two unknown words
+    assert_eq!(
+        WordRecovery::new("? winner ? year wave sausage worth useful legal winner thank", Language::English).err(),
+        Some(BIP32Error::TooManyUnknownWords(3))
+    );
+    assert_eq!(
+        WordRecovery::new("legal winner thank year wave sausage worth useful legal winner", Language::English).err(),
+        Some(BIP32Error::InvalidWordsCount(10))
+    );
+}
+
+#[test]
+fn recover_transposed_words() {
+    // (Written phrase, Expected phrase, Expected transposition, Expected rank in the candidates)
+    let list = vec![
+        (
+            "legal winner thank wave year sausage worth useful legal winner thank yellow",
+            PHRASE,
+            Transposition::AdjacentSwap(3),
+            0,
+        ),
+        // 3 columns grid read column by column
+        (
+            "legal year worth winner winner wave useful thank thank sausage legal yellow",
+            PHRASE,
+            Transposition::ColumnReading(3),
+            0,
+        ),
+        // 3 columns grid read from the last row, after the grids read column by column
+        (
+            "advice cage above acoustic avoid letter absurd amount doctor letter advice cage",
+            PHRASE_2,
+            Transposition::ReversedRows(3),
+            2,
+        ),
+        // Any swap of two words, after the adjacent swaps
+        (
+            "avoid advice cage absurd amount doctor acoustic letter letter advice cage above",
+            PHRASE_2,
+            Transposition::PairSwap(0, 7),
+            2,
+        ),
+    ];
+
+    for (written_phrase, phrase, transposition, rank) in list {
+        let candidates = recovery::recover_transpositions(written_phrase, Language::English).unwrap();
+
+        assert_eq!(candidates[rank].mnemonic().get_phrase(), phrase);
+        assert_eq!(candidates[rank].transposition(), transposition);
+    }
+}
+
+#[test]
+fn recover_transposed_words_should_err() {
+    assert_eq!(
+        recovery::recover_transpositions("legal winner thank wave year sausage worth useful legal winner thank", Language::English).err(),
+        Some(BIP32Error::InvalidWordsCount(11))
+    );
+    assert_eq!(
+        recovery::recover_transpositions("legal winner thank wave year sausage worth useful legal winner thank bitcoin", Language::English).err(),
+        Some(BIP32Error::UnknownWord("bitcoin".to_owned()))
+    );
+    assert_eq!(
+        recovery::recover_transpositions(PHRASE, Language::English).err(),
+        Some(BIP32Error::ChecksumAlreadyValid)
+    );
+}
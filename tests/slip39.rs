@@ -0,0 +1,278 @@
+use bip39::slip39::{self, GroupSpec, Share};
+use bip39::{BIP32Error, Entropy, ExtendedPrivateKey};
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+
+#[test]
+fn combine_mnemonics_from_vectors() {
+    // Vectors from https://github.com/trezor/python-shamir-mnemonic/blob/master/vectors.json, passphrase is "TREZOR"
+    // [Description, Mnemonics, Master secret, BIP32 root key], the mnemonics without master secret are invalid
+    let vectors: Vec<serde_json::Value> =
+        serde_json::from_str(include_str!("vectors/slip39_vectors.json")).unwrap();
+
+    for vector in vectors.iter() {
+        let description = vector[0].as_str().unwrap();
+        let mnemonics: Vec<String> = vector[1]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|mnemonic| mnemonic.as_str().unwrap().to_owned())
+            .collect();
+        let master_secret = vector[2].as_str().unwrap();
+
+        let result = slip39::combine_mnemonics(&mnemonics, "TREZOR");
+        if master_secret.is_empty() {
+            assert!(result.is_err(), "{}", description);
+            continue;
+        }
+
+        let result = result.unwrap_or_else(|e| panic!("{}: {:?}", description, e));
+        assert_eq!(result.to_hex(), master_secret, "{}", description);
+        assert_eq!(
+            ExtendedPrivateKey::new_master(result.as_ref())
+                .unwrap()
+                .to_string(),
+            vector[3].as_str().unwrap(),
+            "{}",
+            description
+        );
+
+        // Encoding the decoded share gives back the same mnemonic
+        for mnemonic in mnemonics.iter() {
+            assert_eq!(
+                &Share::from_mnemonic(mnemonic).unwrap().to_string(),
+                mnemonic
+            );
+        }
+    }
+}
+
+#[test]
+fn combine_invalid_mnemonics_from_vectors_should_err() {
+    // Built from the vectors of https://github.com/trezor/python-shamir-mnemonic/blob/master/vectors.json
+    let list = vec![
+        (
+            "Mnemonic with invalid checksum (128 bits)",
+            vec!["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision kidney"],
+            BIP32Error::InvalidShareChecksum,
+        ),
+        (
+            "Basic sharing 2-of-3 (128 bits), only one share",
+            vec!["shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed"],
+            BIP32Error::InvalidShareSet(String::from("2 shares are required for the group 0, 1 provided")),
+        ),
+        (
+            "Mnemonics with group sharing, only one group",
+            vec!["eraser senior beard romp adorn nuclear spill corner cradle style ancient family general leader ambition exchange unusual garlic promise voice"],
+            BIP32Error::InvalidShareSet(String::from("2 groups are required, 1 provided")),
+        ),
+        (
+            "Mnemonics of different master secrets",
+            vec![
+                "duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard",
+                "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking",
+            ],
+            BIP32Error::InvalidShareSet(String::from("the shares don't belong to the same master secret")),
+        ),
+    ];
+
+    for (description, mnemonics, error) in list {
+        let mnemonics: Vec<String> = mnemonics
+            .iter()
+            .map(|mnemonic| mnemonic.to_string())
+            .collect();
+
+        assert_eq!(
+            slip39::combine_mnemonics(&mnemonics, "TREZOR"),
+            Err(error),
+            "{}",
+            description
+        );
+    }
+}
+
+#[test]
+fn generate_and_combine_shares_with_groups() {
+    let master_secret = hex::decode("7c3397a292a5941682d7a4ae2d898d11").unwrap();
+    let groups = [
+        GroupSpec::new(1, 1),
+        GroupSpec::new(2, 3),
+        GroupSpec::new(3, 5),
+    ];
+
+    let shares = slip39::generate_shares(2, &groups, &master_secret, "TREZOR", true, 0).unwrap();
+    assert_eq!(
+        shares
+            .iter()
+            .map(|group| group.len())
+            .collect::<Vec<usize>>(),
+        vec![1, 3, 5]
+    );
+
+    // Any 2 groups, with the member threshold of each group
+    let combinations = vec![
+        vec![
+            shares[0][0].clone(),
+            shares[1][2].clone(),
+            shares[1][0].clone(),
+        ],
+        vec![
+            shares[1][1].clone(),
+            shares[1][2].clone(),
+            shares[2][4].clone(),
+            shares[2][0].clone(),
+            shares[2][2].clone(),
+        ],
+        vec![
+            shares[2][1].clone(),
+            shares[2][2].clone(),
+            shares[2][3].clone(),
+            shares[0][0].clone(),
+        ],
+    ];
+    for combination in combinations {
+        let mnemonics: Vec<String> = combination.iter().map(|share| share.to_string()).collect();
+
+        assert_eq!(
+            slip39::combine_shares(&combination, "TREZOR")
+                .unwrap()
+                .as_ref(),
+            master_secret
+        );
+        assert_eq!(
+            slip39::combine_mnemonics(&mnemonics, "TREZOR")
+                .unwrap()
+                .as_ref(),
+            master_secret
+        );
+
+        // A wrong passphrase gives another master secret
+        assert_ne!(
+            slip39::combine_shares(&combination, "").unwrap().as_ref(),
+            master_secret
+        );
+    }
+
+    // Not enough members in the group 2
+    assert!(slip39::combine_shares(
+        &[
+            shares[0][0].clone(),
+            shares[2][1].clone(),
+            shares[2][2].clone()
+        ],
+        "TREZOR"
+    )
+    .is_err());
+}
+
+#[test]
+fn generate_shares_from_entropy() {
+    let entropy = Entropy::from_hex(
+        "9f6a2878b2520799a44ef18bc7df394e7061a224d2c33cd015b157d746869863".to_owned(),
+    )
+    .unwrap();
+
+    let shares =
+        slip39::generate_shares_from_entropy(1, &[GroupSpec::new(3, 5)], &entropy, "", false, 1)
+            .unwrap();
+    let mnemonics: Vec<String> = shares[0][2..]
+        .iter()
+        .map(|share| share.to_string())
+        .collect();
+
+    assert_eq!(mnemonics[0].split(' ').count(), 33);
+    assert_eq!(shares[0][0].iteration_exponent(), 1);
+    assert_eq!(
+        slip39::combine_mnemonics_to_entropy(&mnemonics, "").unwrap(),
+        entropy
+    );
+}
+
+#[test]
+fn generate_shares_with_rng() {
+    // Same seed, same RNG output : the identifier and the shares are reproducible
+    let master_secret = b"ABCDEFGHIJKLMNOP";
+    let groups = [GroupSpec::new(2, 3), GroupSpec::new(1, 1)];
+    let generate = |seed: u64| -> Vec<String> {
+        let mut rng = ChaCha20Rng::seed_from_u64(seed);
+        slip39::generate_shares_with_rng(1, &groups, master_secret, "", true, 0, &mut rng)
+            .unwrap()
+            .iter()
+            .flatten()
+            .map(|share| share.to_string())
+            .collect()
+    };
+
+    let mnemonics = generate(42);
+    assert_eq!(mnemonics.len(), 4);
+    assert_eq!(generate(42), mnemonics);
+    assert_ne!(generate(7), mnemonics);
+    assert_eq!(
+        slip39::combine_mnemonics(&mnemonics[1..3], "")
+            .unwrap()
+            .as_ref(),
+        master_secret
+    );
+
+    let entropy = Entropy::from_hex("00112233445566778899aabbccddeeff".to_owned()).unwrap();
+    let mut rng = ChaCha20Rng::seed_from_u64(42);
+    let shares =
+        slip39::generate_shares_from_entropy_with_rng(1, &groups, &entropy, "", true, 0, &mut rng)
+            .unwrap();
+    let mut rng = ChaCha20Rng::seed_from_u64(42);
+    let other_shares =
+        slip39::generate_shares_from_entropy_with_rng(1, &groups, &entropy, "", true, 0, &mut rng)
+            .unwrap();
+    assert_eq!(shares[0][0].to_string(), other_shares[0][0].to_string());
+}
+
+#[test]
+fn generate_shares_with_invalid_parameters_should_err() {
+    let master_secret = [0u8; 16];
+
+    assert_eq!(
+        slip39::generate_shares(
+            1,
+            &[GroupSpec::new(1, 1)],
+            &master_secret[..15],
+            "",
+            false,
+            0
+        ),
+        Err(BIP32Error::InvalidMasterSecretLength(15))
+    );
+    assert_eq!(
+        slip39::generate_shares(1, &[GroupSpec::new(1, 1)], &master_secret, "é", false, 0),
+        Err(BIP32Error::InvalidPassphrase)
+    );
+    assert!(
+        slip39::generate_shares(2, &[GroupSpec::new(1, 1)], &master_secret, "", false, 0).is_err()
+    );
+    assert!(
+        slip39::generate_shares(1, &[GroupSpec::new(1, 3)], &master_secret, "", false, 0).is_err()
+    );
+    assert!(
+        slip39::generate_shares(1, &[GroupSpec::new(4, 3)], &master_secret, "", false, 0).is_err()
+    );
+    assert!(
+        slip39::generate_shares(1, &[GroupSpec::new(2, 17)], &master_secret, "", false, 0).is_err()
+    );
+    assert!(
+        slip39::generate_shares(1, &[GroupSpec::new(2, 3)], &master_secret, "", false, 16).is_err()
+    );
+    // 257 groups must not wrap around to 1 group
+    assert!(matches!(
+        slip39::generate_shares(
+            1,
+            &[GroupSpec::new(1, 1); 257],
+            &master_secret,
+            "",
+            false,
+            0
+        ),
+        Err(BIP32Error::InvalidSharingParameters(_))
+    ));
+    assert!(matches!(
+        slip39::generate_shares(1, &[GroupSpec::new(1, 1); 17], &master_secret, "", false, 0),
+        Err(BIP32Error::InvalidSharingParameters(_))
+    ));
+}